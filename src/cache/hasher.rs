@@ -0,0 +1,63 @@
+use std::hash::Hasher;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64 bits FNV-1a. Unlike the hasher of the standard library, it gives the same keys from one build
+/// to the other, so that a run can find the cache files written by a previous one.
+#[derive(Clone, Debug)]
+pub struct CacheHasher {
+    state: u64,
+}
+
+impl CacheHasher {
+    pub fn new() -> Self {
+        Self { state: FNV_OFFSET_BASIS }
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+}
+
+impl Default for CacheHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for CacheHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    // Fixed sizes and byte order, whatever the platform
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        // Reference values of the 64 bits FNV-1a
+        assert_eq!(CacheHasher::new().finish(), 0xcbf29ce484222325);
+        let mut hasher = CacheHasher::new();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+    }
+}
@@ -0,0 +1,17 @@
+use std::io::{Error, ErrorKind};
+
+mod hasher;
+mod writer;
+mod reader;
+
+pub use hasher::CacheHasher;
+pub use writer::CacheWriter;
+pub use reader::CacheReader;
+
+/// Start of every cache file, followed by the format version, the kind of content and the key of its input
+const MAGIC: &[u8; 8] = b"RTCACHE\0";
+const VERSION: u32 = 1;
+
+fn invalid_data(path: &str, message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("{}: {}", path, message))
+}
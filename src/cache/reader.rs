@@ -0,0 +1,136 @@
+use crate::cache::{CacheHasher, MAGIC, VERSION, invalid_data};
+
+use std::fs;
+use std::hash::Hasher;
+use std::io::Error;
+
+/// Cache file being read back, once its header and checksum were checked. The reads
+/// fail rather than panic when the payload doesn't have the expected layout.
+pub struct CacheReader {
+    path: String,
+    data: Vec<u8>,
+    position: usize,
+}
+
+impl CacheReader {
+    /// Opens the cache file written for `kind` and `key`: any other file is invalid data
+    pub fn open(path: &str, kind: &[u8; 4], key: u64) -> Result<Self, Error> {
+        let mut data = fs::read(path)?;
+
+        let header_size = MAGIC.len() + 4 + kind.len() + 8;
+        if data.len() < header_size + 8 {
+            return Err(invalid_data(path, "truncated cache file"));
+        }
+        let checksum_position = data.len() - 8;
+        let mut checksum = CacheHasher::new();
+        checksum.write(&data[..checksum_position]);
+        if data[checksum_position..] != checksum.finish().to_le_bytes() {
+            return Err(invalid_data(path, "corrupted cache file"));
+        }
+        data.truncate(checksum_position);
+
+        let mut reader = Self { path: path.to_string(), data, position: 0 };
+        if reader.read_bytes(MAGIC.len())? != MAGIC || reader.read_u32()? != VERSION {
+            return Err(invalid_data(path, "not a cache file of this version"));
+        }
+        if reader.read_bytes(kind.len())? != kind {
+            return Err(invalid_data(path, "cache file of another kind"));
+        }
+        if reader.read_u64()? != key {
+            return Err(invalid_data(path, "cache file of another input"));
+        }
+
+        Ok(reader)
+    }
+
+    pub fn invalid(&self, message: &str) -> Error {
+        invalid_data(&self.path, message)
+    }
+
+    fn read_bytes(&mut self, count: usize) -> Result<&[u8], Error> {
+        if self.data.len() - self.position < count {
+            return Err(self.invalid("unexpected end of cache file"));
+        }
+        self.position += count;
+        Ok(&self.data[self.position - count..self.position])
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, Error> {
+        Ok(f64::from_bits(self.read_u64()?))
+    }
+
+    /// Length of an array whose elements take at least `element_size` bytes each,
+    /// checked against what's left so that a bad length can't trigger a huge allocation
+    pub fn read_len(&mut self, element_size: usize) -> Result<usize, Error> {
+        let len = self.read_u64()?;
+        let left = (self.data.len() - self.position) as u64;
+        if len.saturating_mul(element_size as u64) > left {
+            return Err(self.invalid("array longer than the cache file"));
+        }
+        Ok(len as usize)
+    }
+
+    /// Checks the whole payload was read
+    pub fn finish(&self) -> Result<(), Error> {
+        if self.position != self.data.len() {
+            return Err(self.invalid("unexpected data at the end of cache file"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheWriter;
+
+    use tempdir::TempDir;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn test_round_trip_and_validation() {
+        let directory = TempDir::new("cache").unwrap();
+        let path = directory.path().join("values.cache");
+        let path = path.to_str().unwrap();
+
+        let mut writer = CacheWriter::new(b"TEST", 42);
+        writer.write_u16(7);
+        writer.write_f64(-1.5);
+        writer.write_usize(3);
+        writer.save(path).unwrap();
+
+        let mut reader = CacheReader::open(path, b"TEST", 42).unwrap();
+        assert_eq!(reader.read_u16().unwrap(), 7);
+        assert_eq!(reader.read_f64().unwrap(), -1.5);
+        assert_eq!(reader.read_u64().unwrap(), 3);
+        assert!(reader.read_u8().is_err());
+
+        // Another key or kind, and a corrupted file, are all rejected
+        assert_eq!(CacheReader::open(path, b"TEST", 43).err().unwrap().kind(), ErrorKind::InvalidData);
+        assert_eq!(CacheReader::open(path, b"MESH", 42).err().unwrap().kind(), ErrorKind::InvalidData);
+        let mut data = fs::read(path).unwrap();
+        data[30] ^= 1;
+        fs::write(path, data).unwrap();
+        assert_eq!(CacheReader::open(path, b"TEST", 42).err().unwrap().kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::cache::{CacheHasher, MAGIC, VERSION};
+
+use std::fs;
+use std::hash::Hasher;
+use std::io::Error;
+use std::path::Path;
+
+/// Cache file being written: a header, a little endian payload and the checksum of both,
+/// so that truncated or corrupted files are detected when reading them back.
+pub struct CacheWriter {
+    data: Vec<u8>,
+}
+
+impl CacheWriter {
+    /// `kind` tells what the file holds, `key` identifies the input it was computed from
+    pub fn new(kind: &[u8; 4], key: u64) -> Self {
+        let mut writer = Self { data: Vec::new() };
+        writer.data.extend_from_slice(MAGIC);
+        writer.write_u32(VERSION);
+        writer.data.extend_from_slice(kind);
+        writer.write_u64(key);
+        writer
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    /// Writes the file under a temporary name first, then renames it: other runs never see it half written
+    pub fn save(mut self, path: &str) -> Result<(), Error> {
+        let mut checksum = CacheHasher::new();
+        checksum.write(&self.data);
+        self.write_u64(checksum.finish());
+
+        if let Some(directory) = Path::new(path).parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let temporary = format!("{}.tmp", path);
+        fs::write(&temporary, &self.data)?;
+        fs::rename(&temporary, path)
+    }
+}
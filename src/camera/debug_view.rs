@@ -0,0 +1,119 @@
+use crate::hittable::{HitRecord, HitDiagnostics};
+use crate::color::Color;
+use crate::vector::Vec3;
+
+use std::rc::Rc;
+
+/// Diagnostic render modes, coloring every pixel by something the primary ray found instead of the light it carries
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DebugView {
+    /// BVH nodes visited, from blue (none) to red (`max` or more)
+    NodeHeatmap { max: usize },
+    /// Primitives intersected, from blue (none) to red (`max` or more)
+    PrimitiveTests { max: usize },
+    /// Normal of the surface itself, from its tangent frame
+    GeometricNormal,
+    /// Normal the material shades with, after bump and normal maps
+    ShadingNormal,
+    Uv,
+    /// Green when the ray hits the outer side of the surface, red the inner one
+    FrontFace,
+    MaterialId,
+    ObjectId,
+}
+
+impl DebugView {
+    pub fn color(&self, hit_record: Option<&HitRecord>, diagnostics: &HitDiagnostics) -> Color {
+        match self {
+            DebugView::NodeHeatmap { max } => return heatmap(diagnostics.node_visits as f64 / *max as f64),
+            DebugView::PrimitiveTests { max } => return heatmap(diagnostics.primitive_tests as f64 / *max as f64),
+            _ => (),
+        }
+
+        let hit_record = match hit_record {
+            Some(hit_record) => hit_record,
+            None => return Color::black(),
+        };
+        match self {
+            DebugView::GeometricNormal => normal_color(&geometric_normal(hit_record)),
+            DebugView::ShadingNormal => normal_color(&hit_record.material.shading_normal(hit_record)),
+            DebugView::Uv => Color::new(hit_record.uv.u.rem_euclid(1.), hit_record.uv.v.rem_euclid(1.), 0.),
+            DebugView::FrontFace => if hit_record.front_face { Color::green() } else { Color::red() },
+            DebugView::MaterialId => hashed_color(Rc::as_ptr(&hit_record.material).cast::<()>() as usize as u64),
+            DebugView::ObjectId => hashed_color(diagnostics.object_id),
+            DebugView::NodeHeatmap { .. } | DebugView::PrimitiveTests { .. } => unreachable!(),
+        }
+    }
+}
+
+/// Normal of the plane spanned by dpdu and dpdv, on the side of the hit record normal
+fn geometric_normal(hit_record: &HitRecord) -> Vec3 {
+    let normal = hit_record.dpdu.cross(&hit_record.dpdv);
+    if normal.near_zero() {
+        return hit_record.normal;
+    }
+
+    let normal = normal.normalized();
+    if normal.dot(&hit_record.normal) < 0. { -normal } else { normal }
+}
+
+fn normal_color(normal: &Vec3) -> Color {
+    Color::from_vec(0.5 * (*normal + Vec3::new(1., 1., 1.)))
+}
+
+/// Blue, cyan, green, yellow then red as the value goes from 0 to 1
+fn heatmap(value: f64) -> Color {
+    const PALETTE: [(f64, f64, f64); 5] = [(0., 0., 1.), (0., 1., 1.), (0., 1., 0.), (1., 1., 0.), (1., 0., 0.)];
+
+    let position = value.clamp(0., 1.) * (PALETTE.len() - 1) as f64;
+    let index = (position as usize).min(PALETTE.len() - 2);
+    let (from, to) = (PALETTE[index], PALETTE[index + 1]);
+    Color::new(from.0, from.1, from.2).lerp(&Color::new(to.0, to.1, to.2), position - index as f64)
+}
+
+/// Bright color picked by the hash of an identifier, so that neighbouring ids look different
+fn hashed_color(id: u64) -> Color {
+    // SplitMix64 finalizer
+    let mut hash = id.wrapping_add(0x9e3779b97f4a7c15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hash ^= hash >> 31;
+
+    let hue = (hash & 0xffff) as f64 / 65536. * 6.;
+    let saturation = 0.5 + 0.5 * ((hash >> 16) & 0xff) as f64 / 255.;
+    let value = 0.6 + 0.4 * ((hash >> 24) & 0xff) as f64 / 255.;
+
+    // HSV to RGB
+    let chroma = value * saturation;
+    let x = chroma * (1. - (hue % 2. - 1.).abs());
+    let (r, g, b) = match hue as usize {
+        0 => (chroma, x, 0.),
+        1 => (x, chroma, 0.),
+        2 => (0., chroma, x),
+        3 => (0., x, chroma),
+        4 => (x, 0., chroma),
+        _ => (chroma, 0., x),
+    };
+    let m = value - chroma;
+    Color::new(r + m, g + m, b + m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heatmap_ends() {
+        let cold = heatmap(0.);
+        assert_eq!((cold.r, cold.g, cold.b), (0., 0., 1.));
+        let hot = heatmap(2.);
+        assert_eq!((hot.r, hot.g, hot.b), (1., 0., 0.));
+    }
+
+    #[test]
+    fn test_hashed_colors_differ() {
+        let (a, b) = (hashed_color(1), hashed_color(2));
+        assert!((a.to_vec() - b.to_vec()).length() > 0.1);
+        assert_eq!(hashed_color(1).to_vec().x(), a.to_vec().x());
+    }
+}
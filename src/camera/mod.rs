@@ -1,5 +1,7 @@
 use crate::ray::Ray;
-use crate::hittable::{HittableList, Hittable};
+use crate::ray_packet::RayPacket;
+use crate::statistics::{self, RayKind, Phase};
+use crate::hittable::{HittableList, Hittable, HitDiagnostics};
 use crate::interval::Interval;
 use crate::color::Color;
 use crate::vector::{Vec3, Point};
@@ -11,6 +13,13 @@ use std::io::Write;
 use indicatif::{ProgressStyle, ProgressBar};
 use itertools::Itertools;
 
+mod debug_view;
+
+pub use debug_view::DebugView;
+
+/// Half width of the wireframe lines, in pixels
+const WIREFRAME_HALF_WIDTH: f64 = 0.5;
+
 pub struct Camera {
     focus_distance: f64,
     defocus_angle: f64,
@@ -39,6 +48,15 @@ pub struct Camera {
 
     pub print_progress: bool,
     pub shuffle_rendering: bool,
+
+    /// Number of rays traced together, from tiles of 2x2, 4x2 or 4x4 pixels (1 to trace single rays).
+    /// Only closest hits go through packets: the path tracer has no shadow rays to trace as occlusion packets.
+    pub packet_size: usize,
+
+    /// Renders a diagnostic view of the scene instead of its lighting
+    pub debug_view: Option<DebugView>,
+    /// Color of the edges of the quads and triangles drawn over the image, if any
+    pub wireframe: Option<Color>,
 }
 
 impl Camera {
@@ -68,6 +86,11 @@ impl Camera {
 
             print_progress: true,
             shuffle_rendering: false,
+
+            packet_size: 1,
+
+            debug_view: None,
+            wireframe: None,
         };
         camera.set(Point::new(0., 0., -1.), Point::new(0., 0., 0.), 1., 0., Vec3::new(0., 1., 0.));
         camera
@@ -116,33 +139,65 @@ impl Camera {
         } else {
             None
         };
-        let mut xs_ys = (0..self.image_info.width).cartesian_product(0..self.image_info.height).collect_vec();
+        let (tile_width, tile_height) = match self.packet_size {
+            1 => (1, 1),
+            4 => (2, 2),
+            8 => (4, 2),
+            16 => (4, 4),
+            size => panic!("Unsupported packet size {}, use 1, 4, 8 or 16", size),
+        };
+        let mut tiles = (0..self.image_info.width).step_by(tile_width)
+            .cartesian_product((0..self.image_info.height).step_by(tile_height))
+            .collect_vec();
         let mut rng = rand::thread_rng();
 
-        // use of xs_ys just so that we can completely shuffle the rendering, which is usefull for the "live" rendering project
+        // use of tiles just so that we can completely shuffle the rendering, which is usefull for the "live" rendering project
         if self.shuffle_rendering {
-            xs_ys.shuffle(&mut rng);
+            tiles.shuffle(&mut rng);
         }
 
         let rendering_start = std::time::Instant::now();
-        for (x, y) in xs_ys {
-            let mut color = Color::black();
-            
+        for (tile_x, tile_y) in tiles {
+            let pixels = (tile_x..(tile_x + tile_width).min(self.image_info.width))
+                .cartesian_product(tile_y..(tile_y + tile_height).min(self.image_info.height))
+                .collect_vec();
+            let mut colors = vec![Color::black(); pixels.len()];
+
             for _sample in 0..self.image_info.samples_per_pixel {
-                let ray = self.get_ray(x, y);
-                color += self.ray_color(&ray, &world, self.image_info.max_depth)
+                if self.debug_view.is_some() || self.wireframe.is_some() {
+                    for (color, &(x, y)) in colors.iter_mut().zip(&pixels) {
+                        let ray = self.get_ray(x, y);
+                        *color += self.debug_color(&ray, world);
+                    }
+                } else if let [(x, y)] = pixels[..] {
+                    let ray = self.get_ray(x, y);
+                    statistics::trace_rays(RayKind::Primary, 1);
+                    colors[0] += self.ray_color(&ray, &world, self.image_info.max_depth)
+                } else {
+                    let rays = pixels.iter().map(|&(x, y)| self.get_ray(x, y)).collect_vec();
+                    statistics::trace_rays(RayKind::Primary, rays.len());
+                    for (color, sample) in colors.iter_mut().zip(self.packet_color(&rays, world, self.image_info.max_depth)) {
+                        *color += sample;
+                    }
+                }
             }
-            
-            // Apply gamma correction
-            color *= 1. / (self.image_info.samples_per_pixel as f64);
-            color = Color::new(color.r.sqrt(), color.g.sqrt(), color.b.sqrt());
-            
-            writter.set_at((x, y), color);
-            if let Some(progress) = &progress_bar {
-                progress.inc(1);
+
+            for (&position, mut color) in pixels.iter().zip(colors) {
+                // Apply gamma correction, debug views being shown as they are
+                color *= 1. / (self.image_info.samples_per_pixel as f64);
+                if self.debug_view.is_none() {
+                    color = Color::new(color.r.sqrt(), color.g.sqrt(), color.b.sqrt());
+                }
+
+                writter.set_at(position, color);
+                if let Some(progress) = &progress_bar {
+                    progress.inc(1);
+                }
             }
         }
 
+        statistics::add_time(Phase::Render, rendering_start.elapsed());
+
         if let Some(progress) = &progress_bar {
             progress.finish();
             println!("Done rendering in {:.2}s.\r", rendering_start.elapsed().as_secs_f64());
@@ -183,18 +238,94 @@ impl Camera {
     
     fn ray_color(&mut self, ray: &Ray, world: &HittableList, depth: usize) -> Color {
         if depth <= 0 {
+            statistics::end_path(self.image_info.max_depth);
             return Color::black();
         }
 
         if let Some(hit_record) = world.hit(ray, &Interval::positive()) {
             let color_from_emission = hit_record.material.emitted(&hit_record.uv, &hit_record.point);
             if let Some((attenuation, scattered_ray)) = hit_record.material.scatter(ray, &hit_record) {
+                statistics::trace_rays(RayKind::Secondary, 1);
                 let color_from_scatter = attenuation * self.ray_color(&scattered_ray, world, depth - 1);
                 return color_from_emission + color_from_scatter;
             } 
+            statistics::end_path(self.image_info.max_depth - depth + 1);
             return color_from_emission;
         }
-    
+
+        statistics::end_path(self.image_info.max_depth - depth + 1);
+        self.background_color(ray)
+    }
+
+    /// Traces the rays as a packet, then their scattered rays as another one, and so on. Primary rays
+    /// of a tile are coherent, as well as the bounces on flat mirrors: the others are traced one by one.
+    fn packet_color(&mut self, rays: &[Ray], world: &HittableList, depth: usize) -> Vec<Color> {
+        if depth == 0 {
+            rays.iter().for_each(|_| statistics::end_path(self.image_info.max_depth));
+            return vec![Color::black(); rays.len()];
+        }
+
+        let packet = RayPacket::new(rays);
+        let mut hits = vec![None; rays.len()];
+        world.hit_packet(&packet, packet.all(), &Interval::positive(), &mut hits);
+
+        let mut colors = Vec::with_capacity(rays.len());
+        let mut attenuations = Vec::new();
+        let mut scattered_rays = Vec::new();
+        for (i, (ray, hit)) in rays.iter().zip(hits).enumerate() {
+            match hit {
+                Some(hit_record) => {
+                    colors.push(hit_record.material.emitted(&hit_record.uv, &hit_record.point));
+                    match hit_record.material.scatter(ray, &hit_record) {
+                        Some((attenuation, scattered_ray)) => {
+                            attenuations.push((i, attenuation));
+                            scattered_rays.push(scattered_ray);
+                        },
+                        None => statistics::end_path(self.image_info.max_depth - depth + 1),
+                    }
+                },
+                None => {
+                    statistics::end_path(self.image_info.max_depth - depth + 1);
+                    colors.push(self.background_color(ray));
+                },
+            }
+        }
+
+        if !scattered_rays.is_empty() {
+            statistics::trace_rays(RayKind::Secondary, scattered_rays.len());
+            let scattered_colors = self.packet_color(&scattered_rays, world, depth - 1);
+            for ((i, attenuation), color) in attenuations.into_iter().zip(scattered_colors) {
+                colors[i] += attenuation * color;
+            }
+        }
+
+        colors
+    }
+
+    /// Color of the debug view, or of the lighting, with the wireframe over it
+    fn debug_color(&mut self, ray: &Ray, world: &HittableList) -> Color {
+        let mut diagnostics = HitDiagnostics::new();
+        let hit_record = world.hit_diagnostics(ray, &Interval::positive(), &mut diagnostics);
+
+        let color = match self.debug_view {
+            Some(view) => view.color(hit_record.as_ref(), &diagnostics),
+            None => {
+                statistics::trace_rays(RayKind::Primary, 1);
+                self.ray_color(ray, world, self.image_info.max_depth)
+            },
+        };
+
+        if let (Some(line_color), Some(hit_record), Some(edge_point)) = (&self.wireframe, &hit_record, diagnostics.edge_point) {
+            // Pixels are pixel_delta_u wide at t = 1, the viewport being there
+            let pixel_width = hit_record.t * self.pixel_delta_u.length();
+            if (hit_record.point - edge_point).length() < WIREFRAME_HALF_WIDTH * pixel_width {
+                return line_color.clone();
+            }
+        }
+        color
+    }
+
+    fn background_color(&self, ray: &Ray) -> Color {
         if let Some(color) = &self.background {
             color.clone()
         } else {
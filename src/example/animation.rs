@@ -0,0 +1,106 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::Point;
+use raytracing::hittable::{BvhTree, Hittable, HittableList, Sphere};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+use raytracing::color::Color;
+
+fn main() -> Result<(), std::io::Error> {
+    animation()
+}
+
+pub fn animation() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/animation";
+    const FRAMES: usize = 24;
+    const WIDTH: usize = 400;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 50;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 12., 30.);
+    const LOOK_AT: Point = Point::new(0., 1., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.5, 0.5, 0.5);
+    let materials = [
+        Lambertian::from_rgb(0.8, 0.3, 0.1),
+        Lambertian::from_rgb(0.1, 0.4, 0.8),
+        Metal::new(Color::new(0.8, 0.8, 0.8), 0.1),
+    ];
+
+    // Spheres bouncing while they orbit, always created in the same order so the tree can be refitted
+    let spheres_at = |frame: usize| {
+        let time = frame as f64 / FRAMES as f64;
+        let mut spheres = HittableList::new();
+        for k in 0..300 {
+            let radius = 2. + (k % 20) as f64 * 0.5;
+            let angle = (k as f64 * 0.7 + time * (1. + (k % 3) as f64)) * 2. * std::f64::consts::PI;
+            let height = 0.3 + 3. * ((time + k as f64 * 0.13) * std::f64::consts::PI).sin().abs();
+            let center = Point::new(radius * angle.cos(), height, radius * angle.sin());
+            spheres += Sphere::new(center, 0.3, materials[k % materials.len()].clone());
+        }
+        spheres
+    };
+
+    let mut tree = BvhTree::from_list(&spheres_at(0));
+    for frame in 0..FRAMES {
+        // Refit the tree to the new positions, only rebuilding it once it got too loose
+        let spheres = spheres_at(frame);
+        tree.refit(&spheres);
+        if tree.needs_rebuild(1.5) {
+            tree = BvhTree::from_list(&spheres);
+        }
+
+        let mut world = HittableList::new();
+        world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground.clone());
+        world += Box::new(tree.clone()) as Box<dyn Hittable>;
+
+        let filepath = format!("{}_{:02}.png", FILEPATH, frame);
+
+        // Image settings
+        let image_info = ImageInfo::from_aspect_ratio(
+            ASPECT_RATIO, 
+            WIDTH, 
+            filepath.clone(), 
+            SAMPLES_PER_PIXEL, 
+            MAX_DEPTH
+        );
+
+        // Camera 
+        let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+        camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+        // Output settings
+        let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+        writter.try_open()?;
+
+        Terminal::cursor_position(&Position{ x: 2, y: 5});
+        println!("* Rendering image \"{}\" (SAH cost {:.2})\r", filepath, tree.sah_cost());
+
+        // Rendering
+        camera.render(&world, &mut *writter);
+
+        // Saving
+        writter.save()?;
+    }
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,261 @@
+use raytracing::material::*;
+use raytracing::texture::{ImageTexture, NoiseTexture};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{BvhTree, HitRecord, Hittable, HittableList, ConstantMedium, Quad, Sphere, triangle_mesh, yaw_rotated_cuboid};
+use raytracing::mesh::PolygonMesh;
+use raytracing::interval::Interval;
+use raytracing::ray::Ray;
+use raytracing::ray_packet::RayPacket;
+use raytracing::color::Color;
+use raytracing::statistics::{self, RayKind};
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use itertools::Itertools;
+
+use std::time::{Duration, Instant};
+
+fn main() {
+    bvh_benchmark()
+}
+
+/// Compares the median split and SAH builders (sequential and parallel), and the binary and 4-wide traversals,
+/// on the geometry of `final_scene` and on a subdivided mesh. The same camera and random rays
+/// go through every tree. Then traces the camera rays, and shadow rays from their hits towards
+/// a light, as packets of 4 to 16 rays: the renderer doesn't trace shadow rays, so this is the only
+/// user of the occlusion packets. The correctness of the trees is covered by the tests,
+/// the fog media of `final_scene` making its hits random.
+pub fn bvh_benchmark() {
+    const RESOLUTION: usize = 400;
+    const RANDOM_RAYS: usize = 200_000;
+
+    let world = final_scene_world();
+    let camera = (Point::new(478., 278., -600.), Point::new(278., 278., 0.));
+    let rays = benchmark_rays(RESOLUTION, RANDOM_RAYS, camera, (Point::new(-1000., 0., -1000.), Point::new(1000., 600., 1000.)));
+    run("final_scene", &world, &rays);
+    run_packets(&world, &rays[..RESOLUTION * RESOLUTION], RESOLUTION, Point::new(278., 554., 279.));
+
+    let world = mesh_world();
+    let camera = (Point::new(0., 1.5, 4.), Point::new(0., 0., 0.));
+    let rays = benchmark_rays(RESOLUTION, RANDOM_RAYS, camera, (Point::new(-1.5, -1.5, -1.5), Point::new(1.5, 1.5, 1.5)));
+    run("subdivided mesh", &world, &rays);
+    run_packets(&world, &rays[..RESOLUTION * RESOLUTION], RESOLUTION, Point::new(3., 5., 4.));
+}
+
+fn run(scene: &str, world: &HittableList, rays: &[Ray]) {
+    const RUNS: usize = 5;
+
+    println!("* {}: {} objects, {} rays, best of {} runs\n", scene, world.objects().borrow().len(), rays.len(), RUNS);
+    println!("{:<16} {:>8} {:>8} {:>12} {:>12} {:>12}", "builder", "nodes", "depth", "build", "traversal", "Mrays/s");
+
+    type Builder = fn(&HittableList) -> BvhTree;
+    type Traversal = fn(&BvhTree, &Ray, &Interval) -> Option<HitRecord>;
+    let configurations: [(&str, Builder, Traversal); 4] = [
+        ("median, binary", BvhTree::from_list_median, BvhTree::hit_binary),
+        ("sah, binary", BvhTree::from_list_sequential, BvhTree::hit_binary),
+        ("sah, parallel", BvhTree::from_list, BvhTree::hit_binary),
+        ("sah, 4-wide", BvhTree::from_list, <BvhTree as Hittable>::hit),
+    ];
+    for (name, builder, traversal_function) in configurations {
+        let mut build = Duration::MAX;
+        let mut traversal = Duration::MAX;
+        let mut tree = builder(world);
+
+        for _ in 0..RUNS {
+            let start = Instant::now();
+            tree = builder(world);
+            build = build.min(start.elapsed());
+
+            let start = Instant::now();
+            for ray in rays {
+                traversal_function(&tree, ray, &Interval::positive());
+            }
+            traversal = traversal.min(start.elapsed());
+        }
+
+        let (nodes, depth) = tree.statistics();
+        let rays_per_second = rays.len() as f64 / traversal.as_secs_f64() / 1e6;
+        println!("{:<16} {:>8} {:>8} {:>12.2?} {:>12.2?} {:>12.2}", name, nodes, depth, build, traversal, rays_per_second);
+    }
+    println!();
+}
+
+/// Traces the camera rays by tiles like `Camera::render`, then shadow rays from their hits towards the light
+fn run_packets(world: &HittableList, camera_rays: &[Ray], resolution: usize, light: Point) {
+    const RUNS: usize = 5;
+
+    let tree = BvhTree::from_list(world);
+    let shadow_rays = camera_rays.iter().map(|ray| {
+        let origin = tree.hit(ray, &Interval::positive()).map_or(ray.at(1.), |hit| hit.point);
+        Ray::new(origin, light - origin, 0.)
+    }).collect_vec();
+    statistics::reset();
+
+    println!("{:<16} {:>12} {:>12} {:>12} {:>12}", "packets", "primary", "Mrays/s", "shadow", "Mrays/s");
+    // Packets of a single ray are traced with the 4-wide traversal instead
+    for size in [1, 4, 8, 16] {
+        let (tile_width, tile_height) = match size {
+            1 => (1, 1),
+            4 => (2, 2),
+            8 => (4, 2),
+            _ => (4, 4),
+        };
+        let tiles = (0..resolution).step_by(tile_width)
+            .cartesian_product((0..resolution).step_by(tile_height))
+            .map(|(tile_x, tile_y)| {
+                (tile_x..(tile_x + tile_width).min(resolution))
+                    .cartesian_product(tile_y..(tile_y + tile_height).min(resolution))
+                    .map(|(x, y)| y * resolution + x)
+                    .collect_vec()
+            })
+            .collect_vec();
+        let primary_packets = tiles.iter().map(|tile| tile.iter().map(|&i| camera_rays[i]).collect_vec()).collect_vec();
+        let shadow_packets = tiles.iter().map(|tile| tile.iter().map(|&i| shadow_rays[i]).collect_vec()).collect_vec();
+
+        let mut primary = Duration::MAX;
+        let mut shadow = Duration::MAX;
+        for _ in 0..RUNS {
+            let start = Instant::now();
+            for rays in &primary_packets {
+                statistics::trace_rays(RayKind::Primary, rays.len());
+                if let [ray] = &rays[..] {
+                    tree.hit(ray, &Interval::positive());
+                } else {
+                    let packet = RayPacket::new(rays);
+                    let mut hits = vec![None; rays.len()];
+                    tree.hit_packet(&packet, packet.all(), &Interval::positive(), &mut hits);
+                }
+            }
+            primary = primary.min(start.elapsed());
+
+            let start = Instant::now();
+            for rays in &shadow_packets {
+                statistics::trace_rays(RayKind::Shadow, rays.len());
+                if let [ray] = &rays[..] {
+                    tree.hit(ray, &Interval::new(0.001, 0.999));
+                } else {
+                    let packet = RayPacket::new(rays);
+                    tree.occluded_packet(&packet, packet.all(), &Interval::new(0.001, 0.999));
+                }
+            }
+            shadow = shadow.min(start.elapsed());
+        }
+
+        let primary_speed = camera_rays.len() as f64 / primary.as_secs_f64() / 1e6;
+        let shadow_speed = shadow_rays.len() as f64 / shadow.as_secs_f64() / 1e6;
+        println!("{:<16} {:>12.2?} {:>12.2} {:>12.2?} {:>12.2}", size, primary, primary_speed, shadow, shadow_speed);
+    }
+    println!();
+
+    // Counters of the packet runs, with the statistics feature
+    if statistics::ENABLED {
+        println!("{}", statistics::report());
+    }
+}
+
+/// Primary rays of a 40 degrees camera, followed by random rays starting in the `origins` box
+fn benchmark_rays(resolution: usize, random_rays: usize, (look_from, look_at): (Point, Point), origins: (Point, Point)) -> Vec<Ray> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut rays = Vec::with_capacity(resolution * resolution + random_rays);
+
+    // Primary rays, in raster order
+    let w = (look_from - look_at).normalized();
+    let u = Vec3::new(0., 1., 0.).cross(&w).normalized();
+    let v = w.cross(&u);
+    let half_size = (40f64.to_radians() / 2.).tan();
+    for (j, i) in (0..resolution).cartesian_product(0..resolution) {
+        let x = (2. * (i as f64 + 0.5) / resolution as f64 - 1.) * half_size;
+        let y = (1. - 2. * (j as f64 + 0.5) / resolution as f64) * half_size;
+        rays.push(Ray::new(look_from, x * u + y * v - w, 0.));
+    }
+
+    // Rays starting anywhere in the scene, like the bounces
+    let (min, max) = origins;
+    for _ in 0..random_rays {
+        let origin = Point::new(rng.gen_range(min.x()..max.x()), rng.gen_range(min.y()..max.y()), rng.gen_range(min.z()..max.z()));
+        let direction = Vec3::new(rng.gen_range(-1.0..1.), rng.gen_range(-1.0..1.), rng.gen_range(-1.0..1.));
+        rays.push(Ray::new(origin, direction, rng.gen_range(0.0..1.)));
+    }
+
+    rays
+}
+
+/// Catmull-Clark subdivided cube, about 50k triangles
+fn mesh_world() -> HittableList {
+    let mesh = PolygonMesh::load_obj("assets/cube.obj").expect("Cube mesh not found").catmull_clark(6);
+    triangle_mesh(&mesh, true, Lambertian::from_rgb(0.7, 0.3, 0.1))
+}
+
+fn final_scene_world() -> HittableList {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    // Textures
+    let earth_texture = ImageTexture::new("assets/earthmap.jpg").expect("Earth texture not found");
+    let noise_texture = NoiseTexture::new(0.1);
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.48, 0.83, 0.53);
+    let light = DiffuseLight::white(7.);
+    let sphere_material = Lambertian::from_rgb(0.7, 0.3, 0.1);
+    let earth_material = Lambertian::from_texture(earth_texture);
+    let noise_material = Lambertian::from_texture(noise_texture);
+
+    let boxes_per_side = 20;
+    // World
+    let mut world = HittableList::new();
+
+    // Boxes
+    for i in 0..boxes_per_side {
+        for j in 0..boxes_per_side {
+            let w = 100.;
+            let size = Vec3::new(w, rng.gen_range(1.0..101.0), w);
+            let position = Vec3::new(-1000. + i as f64 * w, 0., -1000. + j as f64 * w) + size / 2.;
+
+            world += yaw_rotated_cuboid(position, size, 0., ground.clone());
+        }
+    }
+
+    // Light
+    world += Quad::new(Point::new(123., 554., 147.), Vec3::new(300., 0., 0.), Vec3::new(0., 0., 265.), light);
+
+    // Moving sphere
+    let center1 = Point::new(400., 400., 200.);
+    let center2 = center1 + Vec3::new(30., 0., 0.);
+    world += Sphere::new_moving(center1, center2, 50., sphere_material);
+
+    world += Sphere::new(Point::new(260., 150., 45.), 50., Dielectric::new(1.5));
+    world += Sphere::new(Point::new(0., 150., 145.), 50., Metal::new(Color::new(0.8, 0.8, 0.9), 1.));
+
+    // Foggy Dielectric sphere
+    let boundary = Sphere::new(Point::new(360., 150., 145.), 70., Dielectric::new(1.5));
+    world += boundary.clone();
+    world += ConstantMedium::from_color(boundary, 0.2, Color::new(0.2, 0.4, 0.9));
+
+    let boundary = Sphere::new(Point::new(0., 0., 0.), 5000., Dielectric::new(1.5));
+    world += ConstantMedium::from_color(boundary, 0.0001, Color::new(1., 1., 1.));
+
+    // Earth sphere
+    world += Sphere::new(Point::new(400., 200., 400.), 100., earth_material);
+
+    // Noise sphere
+    world += Sphere::new(Point::new(220., 280., 300.), 80., noise_material);
+
+    // Cluster of small spheres
+    let white = Lambertian::from_rgb(0.73, 0.73, 0.73);
+    let ns = 1000;
+    let translation = Vec3::new(-100., 270., 395.);
+    let rotation_rad = 15f64.to_radians();
+    let (s, c) = rotation_rad.sin_cos();
+    for _ in 0..ns {
+        let random = Point::new(rng.gen_range(0.0..165.), rng.gen_range(0.0..165.), rng.gen_range(0.0..165.));
+        let rotated_random = Vec3::new(
+            random.x() * c + random.z() * s,
+            random.y(),
+            random.z() * c - random.x() * s
+        );
+        let center = translation + rotated_random;
+        world += Sphere::new(center, 10., white.clone());
+    }
+
+    world
+}
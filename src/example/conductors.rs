@@ -0,0 +1,88 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, Quad};
+use raytracing::texture::{CheckerTexture, SolidColor};
+use raytracing::image_info::ImageInfo;
+use raytracing::color::Color;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    conductors()
+}
+
+pub fn conductors() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/conductors.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 7., 16.);
+    const LOOK_AT: Point = Point::new(0., 0.5, 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let checker = CheckerTexture::new(SolidColor::from_rgb(0.2, 0.2, 0.2), SolidColor::from_rgb(0.8, 0.8, 0.8), 0.5);
+    let ground = Lambertian::from_texture(checker);
+
+    // World: a row per preset, from smooth to rough, and brushed aluminum on the right
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(-20., 0., 20.), Vec3::new(40., 0., 0.), Vec3::new(0., 0., -40.), ground);
+
+    let presets: [fn(f64) -> std::rc::Rc<dyn Material>; 4] = [Conductor::gold, Conductor::copper, Conductor::aluminum, Conductor::silver];
+    for (row, preset) in presets.iter().enumerate() {
+        for (column, roughness) in [0.05, 0.25, 0.5].into_iter().enumerate() {
+            let center = Point::new(-4. + column as f64 * 2.2, 0.9, -3. + row as f64 * 2.2);
+            world += Sphere::new(center, 0.9, preset(roughness));
+        }
+    }
+    let brushed = Conductor::anisotropic(Color::new(1.657, 0.880, 0.521), Color::new(9.224, 6.270, 4.837), 0.6, 0.1);
+    world += Sphere::new(Point::new(3.5, 1.5, 0.), 1.5, brushed);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,94 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Csg, Sphere, axis_aligned_cuboid};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    csg()
+}
+
+pub fn csg() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/csg.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(2., 5., 12.);
+    const LOOK_AT: Point = Point::new(0., 1., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.5, 0.5, 0.5);
+    let red = Lambertian::from_rgb(0.7, 0.1, 0.1);
+    let blue = Lambertian::from_rgb(0.1, 0.2, 0.7);
+    let yellow = Lambertian::from_rgb(0.8, 0.7, 0.1);
+    let glass = Dielectric::new(1.5);
+
+    // World
+    let mut world = HittableList::new();
+    world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground);
+
+    // Sphere minus a cuboid
+    let sphere = Sphere::new(Point::new(-3., 1., 0.), 1., red.clone());
+    let cube = Box::new(axis_aligned_cuboid(Point::new(-2.5, 1.5, 0.5), Vec3::new(1.2, 1.2, 1.2), yellow.clone()));
+    world += Csg::difference(sphere, cube);
+
+    // Intersection of a sphere and a cuboid
+    let sphere = Sphere::new(Point::new(0., 1., 0.), 1.2, blue.clone());
+    let cube = Box::new(axis_aligned_cuboid(Point::new(0., 1., 0.), Vec3::new(1.8, 1.8, 1.8), yellow.clone()));
+    world += Csg::intersection(sphere, cube);
+
+    // Glass lens: intersection of two spheres
+    let left = Sphere::new(Point::new(2.2, 1., 0.), 1.5, glass.clone());
+    let right = Sphere::new(Point::new(3.8, 1., 0.), 1.5, glass);
+    world += Csg::intersection(left, right);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
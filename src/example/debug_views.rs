@@ -0,0 +1,114 @@
+use raytracing::camera::{Camera, DebugView};
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Instance, Quad, Sphere, axis_aligned_cuboid, triangle_mesh};
+use raytracing::mesh::PolygonMesh;
+use raytracing::texture::{ImageTexture, NoiseTexture};
+use raytracing::transform::Transform;
+use raytracing::image_info::ImageInfo;
+use raytracing::color::Color;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    debug_views()
+}
+
+pub fn debug_views() -> Result<(), std::io::Error> {
+    // Constants
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 4;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 5., 14.);
+    const LOOK_AT: Point = Point::new(0., 1., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    // Every view, and the lit scene with the wireframe over it
+    let views = [
+        ("output/debug_node_heatmap.png", Some(DebugView::NodeHeatmap { max: 16 })),
+        ("output/debug_primitive_tests.png", Some(DebugView::PrimitiveTests { max: 8 })),
+        ("output/debug_geometric_normal.png", Some(DebugView::GeometricNormal)),
+        ("output/debug_shading_normal.png", Some(DebugView::ShadingNormal)),
+        ("output/debug_uv.png", Some(DebugView::Uv)),
+        ("output/debug_front_face.png", Some(DebugView::FrontFace)),
+        ("output/debug_material_id.png", Some(DebugView::MaterialId)),
+        ("output/debug_object_id.png", Some(DebugView::ObjectId)),
+        ("output/debug_wireframe.png", None),
+    ];
+
+    welcome_message();
+
+    // Textures
+    let domes = ImageTexture::new("assets/domes_normal.png").expect("Normal map not found");
+    let noise = NoiseTexture::new(4.);
+
+    // Materials
+    let ground = NormalMap::new(Lambertian::from_rgb(0.5, 0.5, 0.5), domes);
+    let orange = BumpMap::new(Lambertian::from_rgb(0.8, 0.4, 0.1), noise, 0.05);
+    let blue = Lambertian::from_rgb(0.2, 0.3, 0.7);
+    let metal = Metal::new(Color::new(0.8, 0.8, 0.9), 0.1);
+
+    // A coarse smooth-shaded sphere, instanced three times
+    let mut ball = HittableList::new();
+    ball += triangle_mesh(&PolygonMesh::sphere(Point::new(0., 0., 0.), 1., 12, 6), true, blue);
+    let ball = ball.to_blas();
+
+    // World
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(-10., 0., 10.), Vec3::new(20., 0., 0.), Vec3::new(0., 0., -20.), ground);
+    world += Sphere::new(Point::new(-3., 1., 0.), 1., orange);
+    world += axis_aligned_cuboid(Point::new(3., 1., 0.), Vec3::new(1.5, 2., 1.5), metal);
+    for x in [-1.5, 0., 1.5] {
+        world += Instance::new(ball.clone(), Transform::scaling(Vec3::new(0.6, 0.6, 0.6)).then_translate(Vec3::new(x, 0.6, 2.)));
+    }
+
+    let world = world.to_bvh();
+
+    for (filepath, view) in views {
+        // Image settings
+        let image_info = ImageInfo::from_aspect_ratio(
+            ASPECT_RATIO, 
+            WIDTH, 
+            filepath.to_string(), 
+            if view.is_some() { SAMPLES_PER_PIXEL } else { 100 }, 
+            MAX_DEPTH
+        );
+
+        // Camera 
+        let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+        camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+        camera.debug_view = view;
+        if view.is_none() {
+            camera.wireframe = Some(Color::white());
+        }
+
+        // Output settings
+        let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+        writter.try_open()?;
+
+        Terminal::cursor_position(&Position{ x: 2, y: 5});
+        println!("* Rendering image \"{}\"\r", filepath);
+
+        // Rendering
+        camera.render(&world, &mut *writter);
+
+        // Saving
+        writter.save()?;
+    }
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,92 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, triangle_mesh};
+use raytracing::mesh::PolygonMesh;
+use raytracing::texture::{ImageTexture, NoiseTexture};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    displacement()
+}
+
+pub fn displacement() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/displacement.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 4., 14.);
+    const LOOK_AT: Point = Point::new(0., 1.2, 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Textures
+    let earth_texture = ImageTexture::new("assets/earthmap.jpg").expect("Earth texture not found");
+    let noise_texture = NoiseTexture::new(2.);
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.5, 0.5, 0.5);
+    let earth = Lambertian::from_texture(earth_texture.clone());
+    let stone = Lambertian::from_rgb(0.6, 0.55, 0.5);
+
+    // World
+    let mut world = HittableList::new();
+    world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground);
+
+    // Sphere tessellated then displaced by the earth map brightness
+    let planet = PolygonMesh::sphere(Point::new(-2.5, 1.5, 0.), 1.5, 256, 128).displaced(&*earth_texture, 0.15);
+    world += triangle_mesh(&planet, true, earth);
+
+    // Cube cage subdivided, then displaced by noise
+    let cube = PolygonMesh::load_obj("assets/cube.obj")?;
+    let rock = cube.transformed(1.2, Vec3::new(2.5, 1.3, 0.)).catmull_clark(5).displaced(&*noise_texture, 0.25);
+    world += triangle_mesh(&rock, true, stone);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
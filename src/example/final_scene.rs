@@ -8,6 +8,7 @@ use raytracing::hittable::{HittableList, ConstantMedium, Quad, Sphere, yaw_rotat
 use raytracing::image_info::ImageInfo;
 use raytracing::terminal::{Terminal, Position};
 use raytracing::color::Color;
+use raytracing::statistics::{self, Phase};
 
 use rand::Rng;
 
@@ -35,6 +36,7 @@ pub fn final_scene() -> Result<(), std::io::Error> {
 
     welcome_message();
 
+    let load_start = std::time::Instant::now();
     let mut rng = rand::thread_rng();
 
     // Textures
@@ -105,8 +107,10 @@ pub fn final_scene() -> Result<(), std::io::Error> {
         world += Sphere::new(center, 10., white.clone());
     }
 
+    statistics::add_time(Phase::Load, load_start.elapsed());
+
     // Convert world to BVH tree
-    let world = world.to_bvh();
+    let world = statistics::time(Phase::BvhBuild, || world.to_bvh());
 
     // Image settings
     let image_info = ImageInfo::from_aspect_ratio(
@@ -133,7 +137,14 @@ pub fn final_scene() -> Result<(), std::io::Error> {
     camera.render(&world, &mut *writter);
 
     // Saving
-    writter.save()?;
+    statistics::time(Phase::Save, || writter.save())?;
+
+    // Counters, with the statistics feature
+    if statistics::ENABLED {
+        let report = statistics::report();
+        println!("{}", report);
+        std::fs::write("output/final_scene_statistics.json", report.to_json())?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,86 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, Quad};
+use raytracing::texture::{CheckerTexture, SolidColor};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    frosted_glass()
+}
+
+pub fn frosted_glass() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/frosted_glass.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 7., 16.);
+    const LOOK_AT: Point = Point::new(0., 0.5, 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let checker = CheckerTexture::new(SolidColor::from_rgb(0.2, 0.2, 0.2), SolidColor::from_rgb(0.8, 0.8, 0.8), 0.5);
+    let ground = Lambertian::from_texture(checker);
+    let light = DiffuseLight::white(4.);
+    let stripes = CheckerTexture::new(SolidColor::from_rgb(0.05, 0.05, 0.05), SolidColor::from_rgb(0.5, 0.5, 0.5), 0.4);
+
+    // World: glass from smooth to ground, a pane whose roughness follows a checker, and a light behind
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(-20., 0., 20.), Vec3::new(40., 0., 0.), Vec3::new(0., 0., -40.), ground);
+    world += Quad::new(Point::new(-6., 0., -4.), Vec3::new(12., 0., 0.), Vec3::new(0., 3., 0.), light);
+
+    for (column, roughness) in [0., 0.1, 0.3, 0.6].into_iter().enumerate() {
+        let center = Point::new(-4.5 + column as f64 * 2.2, 1., 0.);
+        world += Sphere::new(center, 1., RoughDielectric::new(1.5, roughness));
+    }
+    world += Quad::new(Point::new(3.5, 0., 1.5), Vec3::new(2., 0., -1.), Vec3::new(0., 2.5, 0.), RoughDielectric::from_texture(1.5, stripes));
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,115 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Curve, CurveKind, Sphere};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+use raytracing::color::Color;
+
+use rand::Rng;
+
+fn main() -> Result<(), std::io::Error> {
+    hair()
+}
+
+pub fn hair() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/hair.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 3., 12.);
+    const LOOK_AT: Point = Point::new(0., 1.2, 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    let mut rng = rand::thread_rng();
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.5, 0.5, 0.5);
+    let skin = Lambertian::from_rgb(0.4, 0.3, 0.25);
+    let brown_hair = Hair::from_melanin(1.3, 0., 0.3, 0.3);
+    let blond_hair = Hair::from_melanin(0.3, 0.2, 0.25, 0.3);
+    let red_hair = Hair::from_color(Color::new(0.6, 0.2, 0.1), 0.3, 0.3);
+
+    // World
+    let mut world = HittableList::new();
+    world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground);
+
+    // Fur ball: round strands sprouting from a sphere, bent by gravity
+    let center = Point::new(-2.2, 1.2, 0.);
+    world += Sphere::new(center, 0.8, skin);
+    for _ in 0..4000 {
+        let normal = Vec3::random_unit_vector();
+        let root = center + 0.8 * normal;
+        let length = rng.gen_range(0.35..0.5);
+        let points = [
+            root,
+            root + normal * (length / 2.),
+            root + normal * length + Vec3::new(0., -0.15, 0.),
+        ];
+        world += Curve::strand(&points, 0.012, 0.004, CurveKind::Cylinder, brown_hair.clone());
+    }
+
+    // Grass patch: flat ribbons
+    for _ in 0..1500 {
+        let root = Point::new(rng.gen_range(-0.8..0.8), 0., rng.gen_range(-0.8..0.8));
+        let lean = Vec3::new(rng.gen_range(-0.3..0.3), 0., rng.gen_range(-0.3..0.3));
+        let height = rng.gen_range(0.6..1.2);
+        let points = [
+            root,
+            root + Vec3::new(0., height / 2., 0.) + lean * 0.3,
+            root + Vec3::new(0., height, 0.) + lean,
+        ];
+        world += Curve::strand(&points, 0.03, 0.002, CurveKind::Flat, blond_hair.clone());
+    }
+
+    // Curls loaded from a file
+    world += Curve::load("assets/curls.curves", CurveKind::Cylinder, red_hair)?;
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
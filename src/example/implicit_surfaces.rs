@@ -0,0 +1,95 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Implicit, Sphere, AABB};
+use raytracing::implicit::{AlgebraicSurface, Metaballs, Translated};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    implicit_surfaces()
+}
+
+pub fn implicit_surfaces() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/implicit_surfaces.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 35.0;
+    const LOOK_FROM: Point = Point::new(0., 6., 22.);
+    const LOOK_AT: Point = Point::new(0., 2., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.5, 0.5, 0.5);
+    let orange = Lambertian::from_rgb(0.8, 0.4, 0.1);
+    let purple = Lambertian::from_rgb(0.5, 0.2, 0.7);
+    let glass = Dielectric::new(1.5);
+
+    // World
+    let mut world = HittableList::new();
+    world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground);
+
+    // Quartic torus, solved exactly with Sturm sequences
+    let torus = Translated::new(AlgebraicSurface::torus(1.5, 0.5), Vec3::new(-6., 0.5, 0.));
+    world += Implicit::new(torus, AABB::from_points(Point::new(-8., 0., -2.), Point::new(-4., 1., 2.)), glass);
+
+    // Tanglecube
+    let tanglecube = Translated::new(AlgebraicSurface::tanglecube(), Vec3::new(0., 2.6, 0.));
+    world += Implicit::new(tanglecube, AABB::from_points(Point::new(-3., -0.4, -3.), Point::new(3., 5.6, 3.)), orange);
+
+    // Metaballs, sampled along the ray
+    let metaballs = Metaballs::new(vec![
+        (Point::new(5., 1., 0.), 1.5),
+        (Point::new(6., 2., 0.5), 1.2),
+        (Point::new(5.5, 2.8, -0.3), 1.),
+    ], 0.2);
+    world += Implicit::new(metaballs, AABB::from_points(Point::new(3., -1., -2.), Point::new(8., 4.5, 2.)), purple);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,102 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Instance, Sphere, axis_aligned_cuboid, triangle_mesh};
+use raytracing::mesh::PolygonMesh;
+use raytracing::texture::NoiseTexture;
+use raytracing::transform::Transform;
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+
+use rand::Rng;
+
+fn main() -> Result<(), std::io::Error> {
+    instancing()
+}
+
+pub fn instancing() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/instancing.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 40.0;
+    const LOOK_FROM: Point = Point::new(0., 25., 60.);
+    const LOOK_AT: Point = Point::new(0., 0., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    let mut rng = rand::thread_rng();
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.45, 0.4, 0.3);
+    let bark = Lambertian::from_rgb(0.35, 0.2, 0.1);
+    let leaves = Lambertian::from_rgb(0.15, 0.45, 0.15);
+
+    // A single tree, built once as a bottom-level BVH
+    let mut tree = HittableList::new();
+    tree += axis_aligned_cuboid(Point::new(0., 0.5, 0.), Vec3::new(0.2, 1., 0.2), bark);
+    let crown = PolygonMesh::sphere(Point::new(0., 1.6, 0.), 0.8, 16, 8).displaced(&*NoiseTexture::new(3.), 0.15);
+    tree += triangle_mesh(&crown, true, leaves);
+    let tree = tree.to_blas();
+
+    // World
+    let mut world = HittableList::new();
+    world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground);
+
+    // A forest of 10,000 instances of that tree, in a top-level BVH
+    for i in 0..100 {
+        for j in 0..100 {
+            let position = Vec3::new(-100. + i as f64 * 2. + rng.gen_range(-0.6..0.6), 0., -150. + j as f64 * 2. + rng.gen_range(-0.6..0.6));
+            let transform = Transform::scaling(Vec3::new(1., rng.gen_range(0.7..1.4), 1.) * rng.gen_range(0.7..1.2))
+                .then_rotate(Vec3::new(0., 1., 0.), rng.gen_range(0.0..360.))
+                .then_translate(position);
+            world += Instance::new(tree.clone(), transform);
+        }
+    }
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,89 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, Quad};
+use raytracing::texture::{CheckerTexture, NoiseTexture, SolidColor};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    layered_materials()
+}
+
+pub fn layered_materials() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/layered_materials.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 7., 16.);
+    const LOOK_AT: Point = Point::new(0., 0.5, 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let checker = CheckerTexture::new(SolidColor::from_rgb(0.2, 0.2, 0.2), SolidColor::from_rgb(0.8, 0.8, 0.8), 0.5);
+    let ground = Lambertian::from_texture(checker);
+
+    let rust = Lambertian::from_rgb(0.4, 0.15, 0.05);
+    let car_paint = Coated::new(Lambertian::from_rgb(0.7, 0.05, 0.05), 1.5, 0.);
+    let varnished_wood = Coated::new(Lambertian::from_texture(NoiseTexture::new(2.)), 1.5, 0.2);
+    let lacquered_gold = Coated::new(Conductor::gold(0.5), 1.5, 0.);
+    let rusty_copper = Mix::new(Conductor::copper(0.2), rust, NoiseTexture::new(3.));
+    let frosted_stripes = Mix::new(Dielectric::new(1.5), Lambertian::from_rgb(0.1, 0.3, 0.7), CheckerTexture::new(SolidColor::gray(0.), SolidColor::gray(1.), 0.25));
+
+    // World
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(-20., 0., 20.), Vec3::new(40., 0., 0.), Vec3::new(0., 0., -40.), ground);
+
+    let materials = [car_paint, varnished_wood, lacquered_gold, rusty_copper, frosted_stripes];
+    for (column, material) in materials.into_iter().enumerate() {
+        world += Sphere::new(Point::new(-4.4 + column as f64 * 2.2, 1., 0.), 1., material);
+    }
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,87 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, Quad};
+use raytracing::texture::{ImageTexture, NoiseTexture};
+use raytracing::image_info::ImageInfo;
+use raytracing::color::Color;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    normal_mapping()
+}
+
+pub fn normal_mapping() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/normal_mapping.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 4., 14.);
+    const LOOK_AT: Point = Point::new(0., 1., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Textures
+    let domes = ImageTexture::new("assets/domes_normal.png").expect("Normal map not found");
+    let noise = NoiseTexture::new(4.);
+
+    // Materials
+    let ground = NormalMap::new(Lambertian::from_rgb(0.5, 0.5, 0.5), domes.clone());
+    let orange = BumpMap::new(Lambertian::from_rgb(0.8, 0.4, 0.1), noise.clone(), 0.05);
+    let metal = NormalMap::new(Metal::new(Color::new(0.8, 0.8, 0.9), 0.), domes);
+    let glass = BumpMap::new(Dielectric::new(1.5), noise, 0.02);
+
+    // World
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(-10., 0., 10.), Vec3::new(20., 0., 0.), Vec3::new(0., 0., -20.), ground);
+    world += Sphere::new(Point::new(-3., 1., 0.), 1., orange);
+    world += Sphere::new(Point::new(0., 1., 0.), 1., metal);
+    world += Sphere::new(Point::new(3., 1., 0.), 1., glass);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,97 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Quad};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+use raytracing::color::Color;
+
+fn main() -> Result<(), std::io::Error> {
+    planar_shapes()
+}
+
+pub fn planar_shapes() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/planar_shapes.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 1.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const BACKGROUND_COLOR: Color = Color { r: 0., g: 0., b: 0. };
+    
+    const VERTICAL_FOV: f64 = 40.0;
+    const LOOK_FROM: Point = Point::new(278., 278., -800.);
+    const LOOK_AT: Point = Point::new(278., 278., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Textures
+
+    // Materials
+    let red = Lambertian::new(Color::new(0.65, 0.05, 0.05));
+    let green = Lambertian::new(Color::new(0.12, 0.45, 0.15));
+    let white = Lambertian::new(Color::new(0.73, 0.73, 0.73));
+    let orange = Lambertian::new(Color::new(1.0, 0.5, 0.0));
+    let teal = Lambertian::new(Color::new(0.2, 0.8, 0.8));
+    let light = DiffuseLight::white(15.);
+
+    // World
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(555.,0.,0.), Vec3::new(0.,555.,0.), Vec3::new(0.,0.,555.), green);
+    world += Quad::new(Point::new(0.,0.,0.), Vec3::new(0.,555.,0.), Vec3::new(0.,0.,555.), red);
+    world += Quad::new(Point::new(0.,0.,0.), Vec3::new(555.,0.,0.), Vec3::new(0.,0.,555.), white.clone());
+    world += Quad::new(Point::new(555., 555., 555.), Vec3::new(-555., 0., 0.), Vec3::new(0., 0., -555.), white.clone());
+    world += Quad::new(Point::new(0., 0., 555.), Vec3::new(555., 0., 0.), Vec3::new(0., 555., 0.), white.clone());
+
+    // Round ceiling light
+    world += Quad::disk(Point::new(278., 554., 278.), Vec3::new(0., -1., 0.), 80., light);
+
+    // Shapes on the back wall
+    world += Quad::triangle(Point::new(80., 120., 554.), Point::new(240., 120., 554.), Point::new(160., 300., 554.), orange);
+    world += Quad::annulus(Point::new(400., 220., 554.), Vec3::new(90., 0., 0.), Vec3::new(0., 90., 0.), 0.5, teal);
+    world += Quad::ellipse(Point::new(278., 60., 278.), Vec3::new(150., 0., 0.), Vec3::new(0., 0., 80.), white.clone());
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+    camera.set_background(BACKGROUND_COLOR);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -0,0 +1,106 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, Quad};
+use raytracing::texture::{CheckerTexture, SolidColor};
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    principled()
+}
+
+pub fn principled() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/principled.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 40.0;
+    const LOOK_FROM: Point = Point::new(1., 9., 16.);
+    const LOOK_AT: Point = Point::new(1., 0.5, -2.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let checker = CheckerTexture::new(SolidColor::from_rgb(0.2, 0.2, 0.2), SolidColor::from_rgb(0.8, 0.8, 0.8), 0.5);
+    let ground = Lambertian::from_texture(checker);
+
+    let material = |parameters: PrincipledParameters| Principled::new(parameters);
+    let red = SolidColor::from_rgb(0.8, 0.1, 0.1);
+
+    // World: a row per parameter swept from 0 to 1, on a red base
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(-20., 0., 20.), Vec3::new(40., 0., 0.), Vec3::new(0., 0., -40.), ground);
+
+    for column in 0..5 {
+        let value = SolidColor::gray(column as f64 / 4.);
+        let rows = [
+            PrincipledParameters { base_color: red.clone(), roughness: value.clone(), ..Default::default() },
+            PrincipledParameters { base_color: red.clone(), metallic: value.clone(), roughness: SolidColor::gray(0.3), ..Default::default() },
+            PrincipledParameters { base_color: red.clone(), clearcoat: value.clone(), roughness: SolidColor::gray(0.8), ..Default::default() },
+            PrincipledParameters { base_color: SolidColor::from_rgb(0.7, 0.9, 0.8), transmission: value.clone(), roughness: SolidColor::gray(0.1), ..Default::default() },
+            PrincipledParameters { base_color: red.clone(), sheen: value, roughness: SolidColor::gray(1.), ..Default::default() },
+        ];
+        for (row, parameters) in rows.into_iter().enumerate() {
+            let center = Point::new(-4.4 + column as f64 * 2.2, 0.9, -6.6 + row as f64 * 2.2);
+            world += Sphere::new(center, 0.9, material(parameters));
+        }
+    }
+
+    // Textures drive the parameters too: metal squares on a rough plastic
+    let squares = CheckerTexture::new(SolidColor::gray(1.), SolidColor::gray(0.), 0.3);
+    let parameters = PrincipledParameters {
+        base_color: SolidColor::from_rgb(0.9, 0.7, 0.3),
+        metallic: squares.clone(),
+        roughness: CheckerTexture::new(SolidColor::gray(0.2), SolidColor::gray(0.7), 0.3),
+        anisotropic: squares,
+        ..Default::default()
+    };
+    world += Sphere::new(Point::new(7., 1.5, -2.), 1.5, material(parameters));
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
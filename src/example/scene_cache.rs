@@ -0,0 +1,48 @@
+use raytracing::material::*;
+use raytracing::mesh::PolygonMesh;
+use raytracing::hittable::{HittableList, triangle_mesh};
+use raytracing::cache::CacheHasher;
+
+use std::hash::Hasher;
+use std::time::Instant;
+
+fn main() -> Result<(), std::io::Error> {
+    scene_cache()
+}
+
+/// Loads a heavy subdivided mesh and its BVH twice: the first time without cache files, so they are
+/// processed, built and saved, the second time from the cache files.
+pub fn scene_cache() -> Result<(), std::io::Error> {
+    const MESH_CACHE: &str = "cache/smooth_cube.mesh";
+    const BVH_CACHE: &str = "cache/smooth_cube.bvh";
+
+    for path in [MESH_CACHE, BVH_CACHE] {
+        if std::path::Path::new(path).exists() {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    for run in ["cold", "cached"] {
+        let start = Instant::now();
+        let (triangles, _world) = load_scene(MESH_CACHE, BVH_CACHE)?;
+        println!("{:<8} {} triangles, loaded in {:.2?}", run, triangles, start.elapsed());
+    }
+
+    Ok(())
+}
+
+/// Number of triangles of the scene, and its BVH
+fn load_scene(mesh_cache: &str, bvh_cache: &str) -> Result<(usize, HittableList), std::io::Error> {
+    const LEVELS: usize = 7;
+
+    let cube = PolygonMesh::load_obj("assets/cube.obj")?;
+
+    // The subdivided mesh depends on the cage and the number of levels
+    let mut key = CacheHasher::new();
+    cube.hash_geometry(&mut key);
+    key.write_usize(LEVELS);
+    let smooth_cube = PolygonMesh::cached(mesh_cache, key.finish(), || cube.catmull_clark(LEVELS))?;
+
+    let world = triangle_mesh(&smooth_cube, true, Lambertian::from_rgb(0.7, 0.3, 0.1));
+    Ok((world.objects().borrow().len(), world.to_bvh_cached(bvh_cache)?))
+}
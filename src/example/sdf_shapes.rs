@@ -0,0 +1,106 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sdf, Sphere, AABB};
+use raytracing::sdf::*;
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+use raytracing::color::Color;
+
+fn main() -> Result<(), std::io::Error> {
+    sdf_shapes()
+}
+
+pub fn sdf_shapes() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/sdf_shapes.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 4., 12.);
+    const LOOK_AT: Point = Point::new(0., 0.8, 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.5, 0.5, 0.5);
+    let orange = Lambertian::from_rgb(0.8, 0.4, 0.1);
+    let blue = Lambertian::from_rgb(0.2, 0.3, 0.8);
+    let gold = Metal::new(Color::new(0.8, 0.6, 0.2), 0.1);
+    let glass = Dielectric::new(1.5);
+
+    // World
+    let mut world = HittableList::new();
+    world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground);
+
+    // Menger sponge
+    let sponge = Translate::new(MengerSponge::new(2., 4), Vec3::new(-4., 1., 0.));
+    world += Sdf::new(sponge, cube(Point::new(-4., 1., 0.), 2.), orange);
+
+    // Twisted box smoothly merged with a torus
+    let twisted = Twist::new(RoundBox::new(Vec3::new(1., 2., 1.), 0.1), 1.);
+    let torus = Torus::new(0.9, 0.2);
+    let blob = Translate::new(SmoothUnion::new(twisted, torus, 0.3), Vec3::new(-1.3, 1., 0.));
+    world += Sdf::with_step_scale(blob, cube(Point::new(-1.3, 1., 0.), 2.4), 0.5, blue);
+
+    // Sphere with a capsule hole
+    let sphere = SdfSphere::new(1.);
+    let capsule = Capsule::new(Point::new(-1.5, 0., 0.), Point::new(1.5, 0., 0.), 0.4);
+    let pierced = Translate::new(Subtraction::new(sphere, capsule, 0.05), Vec3::new(1.3, 1., 0.));
+    world += Sdf::new(pierced, cube(Point::new(1.3, 1., 0.), 2.), glass);
+
+    // Mandelbulb
+    let bulb = Translate::new(Mandelbulb::new(8., 12), Vec3::new(4., 1.2, 0.));
+    world += Sdf::with_step_scale(bulb, cube(Point::new(4., 1.2, 0.), 2.4), 0.8, gold);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn cube(center: Point, size: f64) -> AABB {
+    let half = Vec3::new(size / 2., size / 2., size / 2.);
+    AABB::from_points(center - half, center + half)
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
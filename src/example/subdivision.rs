@@ -0,0 +1,99 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, triangle_mesh};
+use raytracing::mesh::PolygonMesh;
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+use raytracing::color::Color;
+
+fn main() -> Result<(), std::io::Error> {
+    subdivision()
+}
+
+pub fn subdivision() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/subdivision.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 35.0;
+    const LOOK_FROM: Point = Point::new(0., 5., 16.);
+    const LOOK_AT: Point = Point::new(0., 1., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let ground = Lambertian::from_rgb(0.5, 0.5, 0.5);
+    let orange = Lambertian::from_rgb(0.8, 0.4, 0.1);
+    let metal = Metal::new(Color::new(0.8, 0.8, 0.9), 0.05);
+    let glass = Dielectric::new(1.5);
+
+    // Meshes
+    let cube = PolygonMesh::load_obj("assets/cube.obj")?;
+    let octahedron = PolygonMesh::load_obj("assets/octahedron.obj")?;
+
+    // World
+    let mut world = HittableList::new();
+    world += Sphere::new(Point::new(0., -1000., 0.), 1000., ground);
+
+    // Catmull-Clark on the cube cage: it converges towards a rounded blob
+    let smooth_cube = cube.clone().transformed(1.5, Vec3::new(-4.5, 1.2, 0.)).catmull_clark(4);
+    world += triangle_mesh(&smooth_cube, true, orange);
+
+    // Same cage with its top face edges creased, which stay sharp
+    let mut creased_cube = cube.transformed(1.5, Vec3::new(0., 1.2, 0.));
+    for (a, b) in [(4, 5), (5, 6), (6, 7), (7, 4)] {
+        creased_cube.add_crease(a, b);
+    }
+    world += triangle_mesh(&creased_cube.catmull_clark(4), true, metal);
+
+    // Loop subdivision of the octahedron
+    let smooth_octahedron = octahedron.transformed(1.8, Vec3::new(4.5, 1.6, 0.)).loop_subdivide(4);
+    world += triangle_mesh(&smooth_octahedron, true, glass);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
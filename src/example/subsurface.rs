@@ -0,0 +1,99 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Sphere, Quad, Subsurface, triangle_mesh};
+use raytracing::mesh::PolygonMesh;
+use raytracing::texture::{CheckerTexture, SolidColor};
+use raytracing::image_info::ImageInfo;
+use raytracing::color::Color;
+use raytracing::terminal::{Terminal, Position};
+
+fn main() -> Result<(), std::io::Error> {
+    subsurface()
+}
+
+pub fn subsurface() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/subsurface.png";
+    const WIDTH: usize = 600;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 500; // Random walks bounce a lot
+
+    const VERTICAL_FOV: f64 = 30.0;
+    const LOOK_FROM: Point = Point::new(0., 4., 16.);
+    const LOOK_AT: Point = Point::new(0., 1., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let checker = CheckerTexture::new(SolidColor::from_rgb(0.2, 0.2, 0.2), SolidColor::from_rgb(0.8, 0.8, 0.8), 0.5);
+    let ground = Lambertian::from_texture(checker);
+    let light = DiffuseLight::white(6.);
+    let unused = Lambertian::new(Color::white()); // The boundaries only give their shape
+
+    // World: wax, skin, milk and marble spheres, a jade mesh, and a light behind them
+    let mut world = HittableList::new();
+    world += Quad::new(Point::new(-20., 0., 20.), Vec3::new(40., 0., 0.), Vec3::new(0., 0., -40.), ground);
+    world += Quad::new(Point::new(-6., 0.5, -4.), Vec3::new(12., 0., 0.), Vec3::new(0., 4., 0.), light);
+
+    // Scattering and absorption coefficients: wax, skin (red light travelling further), milk and marble
+    let media = [
+        (Color::new(19.8, 18., 12.), Color::new(0.2, 2., 8.), 0.),
+        (Color::new(37., 44., 50.), Color::new(0.15, 0.85, 2.4), 0.8),
+        (Color::new(50., 50., 50.), Color::new(0.005, 0.005, 0.025), 0.8),
+        (Color::new(10., 10., 10.), Color::new(0.01, 0.01, 0.01), 0.),
+    ];
+    for (column, (sigma_s, sigma_a, g)) in media.into_iter().enumerate() {
+        let boundary = Sphere::new(Point::new(-4.5 + column as f64 * 2.2, 1., 0.), 1., unused.clone());
+        world += Subsurface::new(boundary, 1.4, 0.1, sigma_s, sigma_a, g);
+    }
+
+    let cube = PolygonMesh::load_obj("assets/cube.obj")?.transformed(0.8, Vec3::new(4.5, 1.2, 0.)).catmull_clark(3);
+    let jade = Box::new(triangle_mesh(&cube, true, unused).to_bvh());
+    world += Subsurface::new(jade, 1.6, 0.05, Color::new(2., 12., 6.), Color::new(4., 0.5, 2.), 0.3);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+    camera.set_background(Color::new(0.05, 0.05, 0.08));
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
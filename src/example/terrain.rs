@@ -0,0 +1,84 @@
+use raytracing::camera::Camera;
+use raytracing::material::*;
+use raytracing::writter::{Writter, GeneralWritter};
+use raytracing::vector::{Point, Vec3};
+use raytracing::hittable::{HittableList, Heightfield, Sphere};
+use raytracing::noise::Perlin;
+use raytracing::image_info::ImageInfo;
+use raytracing::terminal::{Terminal, Position};
+use raytracing::color::Color;
+
+fn main() -> Result<(), std::io::Error> {
+    terrain()
+}
+
+pub fn terrain() -> Result<(), std::io::Error> {
+    // Constants
+    const FILEPATH: &str = "output/terrain.png";
+    const WIDTH: usize = 800;
+    const ASPECT_RATIO: f64 = 16. / 9.;
+
+    const SAMPLES_PER_PIXEL: usize = 100;
+    const MAX_DEPTH: usize = 50;
+
+    const VERTICAL_FOV: f64 = 40.0;
+    const LOOK_FROM: Point = Point::new(0., 30., -60.);
+    const LOOK_AT: Point = Point::new(0., 5., 0.);
+    const DEFOCUS_ANGLE: f64 = 0.;
+    const UP: Point = Point::new(0., 1., 0.);
+    let focus_distance = (LOOK_FROM - LOOK_AT).length();
+
+    welcome_message();
+
+    // Materials
+    let grass = Lambertian::from_rgb(0.35, 0.5, 0.25);
+    let water = Metal::new(Color::new(0.3, 0.45, 0.6), 0.05);
+
+    // World
+    let mut world = HittableList::new();
+
+    // Noise terrain, 256x256 samples
+    let noise = Perlin::new();
+    world += Heightfield::from_noise(&noise, 256, 4., Point::new(-50., 0., -50.), Vec3::new(100., 20., 100.), grass);
+
+    // Lake level
+    world += Sphere::new(Point::new(0., -10000. + 4., 0.), 10000., water);
+
+    let world = world.to_bvh();
+
+    // Image settings
+    let image_info = ImageInfo::from_aspect_ratio(
+        ASPECT_RATIO, 
+        WIDTH, 
+        FILEPATH.to_string(), 
+        SAMPLES_PER_PIXEL, 
+        MAX_DEPTH
+    );
+
+    // Camera 
+    let mut camera = Camera::new(VERTICAL_FOV, image_info.clone());
+    camera.set(LOOK_FROM, LOOK_AT, focus_distance, DEFOCUS_ANGLE, UP);
+
+    // Output settings
+    let mut writter: Box<dyn Writter> = Box::new(GeneralWritter::new(image_info.clone()));
+    writter.try_open()?;
+
+    Terminal::cursor_position(&Position{ x: 2, y: 5});
+    println!("* Rendering image \"{}\"\r", FILEPATH);
+
+    // Rendering
+    camera.render(&world, &mut *writter);
+
+    // Saving
+    writter.save()?;
+
+    Ok(())
+}
+
+fn welcome_message() {
+    Terminal::clear_screen();
+    Terminal::cursor_position(&Position{ x: 0, y: 1});
+    println!("{}\r", Terminal::repeated('='));
+    println!("{}\r", Terminal::centered(" Shitty Raytracer ", '='));
+    println!("{}\r", Terminal::repeated('='));
+}
@@ -24,9 +24,9 @@ impl AABB {
 
     pub fn empty() -> Self {
         Self {
-            x: Interval::new(0., 0.), // ! MIGHT HAVE TO CHANGE THAT (+inf, -inf) ? 
-            y: Interval::new(0., 0.), // ! MIGHT HAVE TO CHANGE THAT (+inf, -inf) ? 
-            z: Interval::new(0., 0.), // ! MIGHT HAVE TO CHANGE THAT (+inf, -inf) ? 
+            x: Interval::empty(),
+            y: Interval::empty(),
+            z: Interval::empty(),
         }
     }
 
@@ -53,7 +53,30 @@ impl AABB {
         }
     }
 
+    pub fn centroid(&self) -> Point {
+        Point::new(
+            (self.x.min + self.x.max) / 2.,
+            (self.y.min + self.y.max) / 2.,
+            (self.z.min + self.z.max) / 2.,
+        )
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        let (dx, dy, dz) = (self.x.size(), self.y.size(), self.z.size());
+        2. * (dx * dy + dy * dz + dz * dx)
+    }
+
     pub fn hit(&self, ray: &Ray, ray_t: &Interval) -> bool {
+        self.hit_interval(ray, ray_t).is_some()
+    }
+
+    /// Part of `ray_t` during which the ray is inside the box, if any.
+    pub fn hit_interval(&self, ray: &Ray, ray_t: &Interval) -> Option<Interval> {
+        // The slabs of an empty axis, from +inf to -inf, wouldn't narrow the interval
+        if self.x.min > self.x.max || self.y.min > self.y.max || self.z.min > self.z.max {
+            return None;
+        }
+
         let mut tmin = ray_t.min;
         let mut tmax = ray_t.max;
 
@@ -75,7 +98,11 @@ impl AABB {
         tmin = f64::min(f64::max(tz1, tmin), f64::max(tz2, tmin));
         tmax = f64::max(f64::min(tz1, tmax), f64::min(tz2, tmax));
 
-        tmin <= tmax
+        if tmin <= tmax {
+            Some(Interval::new(tmin, tmax))
+        } else {
+            None
+        }
     }
     
     pub fn x(&self) -> &Interval {
@@ -109,4 +136,11 @@ mod test {
         let ray_t = Interval::positive();
         assert!(bbox.hit(&ray, &ray_t));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_empty_is_never_hit() {
+        let ray = Ray::new(Vec3::new(0.5, 0.5, -0.5), Vec3::new(0., 0., 1.), 0.);
+        assert!(!AABB::empty().hit(&ray, &Interval::positive()));
+        assert!(!AABB::empty().hit(&ray, &Interval::universe()));
+    }
+}
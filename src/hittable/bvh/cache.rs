@@ -0,0 +1,212 @@
+use crate::cache::{CacheHasher, CacheReader, CacheWriter};
+use crate::hittable::{HittableList, AABB};
+use crate::hittable::bvh::{BvhTree, LinearNode, wide, SAH_BINS, SAH_TRAVERSAL_COST, MAX_LEAF_SIZE, MAX_DEPTH, BALANCED_DEPTH};
+use crate::interval::Interval;
+
+use std::hash::Hasher;
+use std::io::Error;
+
+const KIND: &[u8; 4] = b"BVH1";
+
+impl BvhTree {
+    /// Loads the tree of the list cached at `path`, or builds it with the SAH and saves it there
+    /// when the file is missing, invalid, or was written for other objects. Failing to save the
+    /// cache is only reported, the tree being built anyway.
+    pub fn from_list_cached(list: &HittableList, path: &str) -> Result<Self, Error> {
+        let key = Self::cache_key(list);
+        match Self::load_cache(list, path, key) {
+            Ok(tree) => Ok(tree),
+            Err(_) => {
+                let tree = Self::from_list(list);
+                if let Err(error) = tree.save_cache(path, key) {
+                    eprintln!("Could not save the BVH cache {}: {}", path, error);
+                }
+                Ok(tree)
+            },
+        }
+    }
+
+    /// The SAH tree only depends on the boxes of the objects, in their order, and on the builder settings
+    fn cache_key(list: &HittableList) -> u64 {
+        let mut hasher = CacheHasher::new();
+        hasher.write_usize(SAH_BINS);
+        hasher.write_f64(SAH_TRAVERSAL_COST);
+        hasher.write_usize(MAX_LEAF_SIZE);
+        hasher.write_usize(BALANCED_DEPTH);
+
+        let objects = list.objects();
+        let objects = objects.borrow();
+        hasher.write_usize(objects.len());
+        for object in objects.iter() {
+            let bbox = object.bounding_box();
+            for axis in 0..3 {
+                hasher.write_f64(bbox.axis(axis).min);
+                hasher.write_f64(bbox.axis(axis).max);
+            }
+        }
+        hasher.finish()
+    }
+
+    fn save_cache(&self, path: &str, key: u64) -> Result<(), Error> {
+        let mut writer = CacheWriter::new(KIND, key);
+
+        writer.write_usize(self.nodes.len());
+        for node in self.nodes.iter() {
+            for axis in 0..3 {
+                writer.write_f64(node.bbox.axis(axis).min);
+                writer.write_f64(node.bbox.axis(axis).max);
+            }
+            writer.write_u32(node.offset);
+            writer.write_u16(node.count);
+            writer.write_u8(node.axis);
+        }
+
+        writer.write_usize(self.indices.len());
+        for &index in self.indices.iter() {
+            writer.write_u32(index as u32);
+        }
+        writer.write_f64(self.build_cost);
+
+        writer.save(path)
+    }
+
+    /// Reads the tree back, checking its structure is one the traversals can walk safely
+    fn load_cache(list: &HittableList, path: &str, key: u64) -> Result<Self, Error> {
+        let mut reader = CacheReader::open(path, KIND, key)?;
+
+        let node_count = reader.read_len(6 * 8 + 4 + 2 + 1)?;
+        let mut nodes = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let mut intervals = [Interval::empty(); 3];
+            for interval in intervals.iter_mut() {
+                *interval = Interval::new(reader.read_f64()?, reader.read_f64()?);
+            }
+            let bbox = AABB::new(intervals[0], intervals[1], intervals[2]);
+            nodes.push(LinearNode { bbox, offset: reader.read_u32()?, count: reader.read_u16()?, axis: reader.read_u8()? });
+        }
+
+        let index_count = reader.read_len(4)?;
+        let mut indices = Vec::with_capacity(index_count);
+        for _ in 0..index_count {
+            indices.push(reader.read_u32()? as usize);
+        }
+        let build_cost = reader.read_f64()?;
+        reader.finish()?;
+
+        // Every object exactly once, and a tree the traversals can walk
+        let objects = list.objects();
+        let objects = objects.borrow();
+        let mut seen = vec![false; objects.len()];
+        if indices.len() != objects.len() || !indices.iter().all(|&i| i < seen.len() && !std::mem::replace(&mut seen[i], true)) {
+            return Err(reader.invalid("cached tree doesn't match the objects"));
+        }
+        let mut covered = 0;
+        if Self::check_subtree(&nodes, 0, 0, &mut covered) != Some(nodes.len()) || covered != indices.len() {
+            return Err(reader.invalid("invalid cached tree"));
+        }
+
+        Ok(Self {
+            wide_nodes: wide::collapse(&nodes).into(),
+            nodes: nodes.into(),
+            primitives: indices.iter().map(|&i| objects[i].clone()).collect(),
+            indices: indices.into(),
+            build_cost,
+        })
+    }
+
+    /// Walks the subtree at `index` and returns the index following it, if the nodes are laid out
+    /// depth-first with the leaves taking the primitives one after the other, no deeper than the stacks
+    fn check_subtree(nodes: &[LinearNode], index: usize, depth: usize, covered: &mut usize) -> Option<usize> {
+        let node = nodes.get(index)?;
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+
+        if node.count > 0 {
+            if node.offset as usize != *covered {
+                return None;
+            }
+            *covered += node.count as usize;
+            Some(index + 1)
+        } else {
+            if node.axis >= 3 {
+                return None;
+            }
+            let second = Self::check_subtree(nodes, index + 1, depth + 1, covered)?;
+            if second != node.offset as usize {
+                return None;
+            }
+            Self::check_subtree(nodes, second, depth + 1, covered)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::{Hittable, Sphere};
+    use crate::material::Lambertian;
+    use crate::color::Color;
+    use crate::ray::Ray;
+    use crate::vector::{Point, Vec3};
+
+    use tempdir::TempDir;
+
+    fn spheres(offset: f64) -> HittableList {
+        let material = Lambertian::new(Color::white());
+        let mut list = HittableList::new();
+        for k in 0..300 {
+            let center = Point::new((k % 17) as f64 * 2. + offset, ((k * 7) % 5) as f64, (k / 17) as f64 * 2.);
+            list += Sphere::new(center, 0.8, material.clone());
+        }
+        list
+    }
+
+    #[test]
+    fn test_cached_tree() {
+        let directory = TempDir::new("bvh_cache").unwrap();
+        let path = directory.path().join("spheres.bvh");
+        let path = path.to_str().unwrap();
+
+        let list = spheres(0.);
+        let built = BvhTree::from_list_cached(&list, path).unwrap();
+        let loaded = BvhTree::load_cache(&list, path, BvhTree::cache_key(&list)).unwrap();
+        assert_eq!(built.indices, loaded.indices);
+        let ray = Ray::new(Point::new(10., 20., 10.), Vec3::new(0.1, -1., 0.2), 0.);
+        assert_eq!(built.hit(&ray, &Interval::positive()).map(|hit| hit.t), loaded.hit(&ray, &Interval::positive()).map(|hit| hit.t));
+
+        // Moved objects don't match the cache anymore: the tree is rebuilt for them
+        let moved = spheres(0.5);
+        assert!(BvhTree::load_cache(&moved, path, BvhTree::cache_key(&moved)).is_err());
+        let rebuilt = BvhTree::from_list_cached(&moved, path).unwrap();
+        assert_eq!(rebuilt.indices, BvhTree::from_list(&moved).indices);
+        assert!(BvhTree::load_cache(&moved, path, BvhTree::cache_key(&moved)).is_ok());
+    }
+
+    #[test]
+    fn test_unwritable_cache() {
+        let directory = TempDir::new("bvh_cache").unwrap();
+        // The directory can't be created, a file being there
+        std::fs::write(directory.path().join("file"), "").unwrap();
+        let path = directory.path().join("file").join("spheres.bvh");
+
+        let list = spheres(0.);
+        let tree = BvhTree::from_list_cached(&list, path.to_str().unwrap()).unwrap();
+        assert_eq!(tree.indices, BvhTree::from_list(&list).indices);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_invalid_structure() {
+        let tree = BvhTree::from_list(&spheres(0.));
+        let mut covered = 0;
+        assert_eq!(BvhTree::check_subtree(&tree.nodes, 0, 0, &mut covered), Some(tree.nodes.len()));
+
+        // An interior node pointing back at its first child would share a subtree
+        let mut nodes = tree.nodes.to_vec();
+        let inner = nodes.iter().position(|node| node.count == 0).unwrap();
+        nodes[inner].offset = inner as u32 + 1;
+        let mut covered = 0;
+        assert_eq!(BvhTree::check_subtree(&nodes, 0, 0, &mut covered), None);
+    }
+}
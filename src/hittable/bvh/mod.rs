@@ -0,0 +1,845 @@
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::ray_packet::RayPacket;
+use crate::statistics;
+use crate::hittable::{Hittable, HittableList, HitRecord, HitDiagnostics, AABB};
+
+use rand::Rng;
+use rayon::prelude::*;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::mem::MaybeUninit;
+
+mod wide;
+mod cache;
+
+use wide::{WideNode, EMPTY};
+
+/// Number of buckets the centroids are sorted into when looking for the best split
+const SAH_BINS: usize = 16;
+/// Cost of visiting a node, relative to the cost of intersecting a primitive
+const SAH_TRAVERSAL_COST: f64 = 0.125;
+/// Leaves can hold up to this many primitives, when it's cheaper than splitting them
+const MAX_LEAF_SIZE: usize = 4;
+/// Size of the traversal stack. Below `BALANCED_DEPTH`, the SAH builder falls back to
+/// balanced splits so the tree never gets deeper than that.
+const MAX_DEPTH: usize = 64;
+const BALANCED_DEPTH: usize = 32;
+/// Nodes with fewer primitives than this are built on the current thread
+const PARALLEL_BUILD_SIZE: usize = 4096;
+/// Above this many primitives, the binning of a node is itself spread across threads, by chunks
+const PARALLEL_BINNING_SIZE: usize = 32 * 1024;
+const BINNING_CHUNK_SIZE: usize = 8192;
+/// Packets with fewer active rays than this are traced one ray after the other
+const MIN_PACKET_RAYS: u32 = 4;
+
+/// Node of the tree while it's being built, before being flattened
+enum BuildNode {
+    Leaf(AABB, Vec<usize>),
+    Node(AABB, usize, Box<BuildNode>, Box<BuildNode>),
+}
+
+/// Node of the flattened tree, in depth-first order: the first child of an interior node
+/// directly follows it, `offset` giving the second one. Leaves use `offset` as the index
+/// of their first primitive.
+#[derive(Clone, Copy)]
+struct LinearNode {
+    bbox: AABB,
+    offset: u32,
+    count: u16,
+    axis: u8,
+}
+
+/// The tree is built and refitted as a binary one, then traversed through its 4-wide version
+#[derive(Clone)]
+pub struct BvhTree {
+    nodes: Rc<[LinearNode]>,
+    wide_nodes: Rc<[WideNode]>,
+    primitives: Rc<[Box<dyn Hittable>]>,
+
+    /// Index in the source list of every primitive, and SAH cost when the tree was built (for refitting)
+    indices: Rc<[usize]>,
+    build_cost: f64,
+}
+
+impl BvhTree {
+    /// Builds the tree with the surface area heuristic
+    pub fn from_list(list: &HittableList) -> Self {
+        let indices: Vec<usize> = (0..list.objects().borrow().len()).collect();
+        Self::from_objects(list.objects().clone(), indices)
+    }
+
+    /// Same tree as `from_list`, built on the current thread only (for comparisons)
+    pub fn from_list_sequential(list: &HittableList) -> Self {
+        let indices: Vec<usize> = (0..list.objects().borrow().len()).collect();
+        Self::build(list.objects().clone(), indices, false)
+    }
+
+    /// Builds the tree with random axis median splits, the former builder (kept for comparisons)
+    pub fn from_list_median(list: &HittableList) -> Self {
+        let indices: Vec<usize> = (0..list.objects().borrow().len()).collect();
+        if indices.is_empty() {
+            panic!("Cannot create BVH tree from empty list");
+        }
+
+        let objects = list.objects();
+        let objects = objects.borrow();
+        let root = Self::build_median(&objects, indices);
+        Self::flatten(root, &objects)
+    }
+
+    /// Binned SAH builder: every node is split where the expected cost of the two children is minimal,
+    /// or becomes a leaf when that's cheaper. The result only depends on the objects order.
+    /// The subtrees of large nodes are built in parallel, as well as the binning of the largest ones.
+    pub fn from_objects(objects: Rc<RefCell<Vec<Box<dyn Hittable>>>>, indices: Vec<usize>) -> Self {
+        Self::build(objects, indices, true)
+    }
+
+    /// The objects can't be shared across threads, only their boxes are: the build works on them alone
+    fn build(objects: Rc<RefCell<Vec<Box<dyn Hittable>>>>, indices: Vec<usize>, parallel: bool) -> Self {
+        if indices.is_empty() {
+            panic!("Cannot create BVH tree from empty list");
+        }
+
+        let boxes: Vec<AABB> = objects.borrow().iter().map(|object| object.bounding_box()).collect();
+        let root = Self::build_sah(&boxes, indices, 0, parallel);
+        Self::flatten(root, &objects.borrow())
+    }
+
+    fn build_median(objects: &[Box<dyn Hittable>], indices: Vec<usize>) -> BuildNode {
+        let mut rng = rand::thread_rng();
+        let axis = rng.gen_range(0..3);
+
+        match indices.len() {
+            1 => BuildNode::Leaf(objects[indices[0]].bounding_box(), indices),
+            _ => {
+                let mut indices = indices;
+                indices.sort_by(|a, b| Self::box_compare(&objects[*a], &objects[*b], axis));
+
+                let right = indices.split_off(indices.len() / 2);
+                let left = Self::build_median(objects, indices);
+                let right = Self::build_median(objects, right);
+                let bbox = left.bounding_box().surrounding_box(&right.bounding_box());
+                BuildNode::Node(bbox, axis, Box::new(left), Box::new(right))
+            },
+        }
+    }
+
+    /// Box unions and counts being exact, whatever the thread splitting the work, the tree stays the same
+    fn build_sah(boxes: &[AABB], indices: Vec<usize>, depth: usize, parallel: bool) -> BuildNode {
+        let parallel = parallel && indices.len() >= PARALLEL_BUILD_SIZE;
+        let bbox = Self::bounds(&indices, parallel, |i| boxes[i]);
+
+        let split = if depth < BALANCED_DEPTH {
+            Self::sah_split(boxes, &indices, &bbox, parallel)
+        } else {
+            Self::balanced_split(boxes, &indices, parallel)
+        };
+
+        match split {
+            Some((axis, left, right)) => {
+                let (left, right) = if parallel {
+                    rayon::join(|| Self::build_sah(boxes, left, depth + 1, true), || Self::build_sah(boxes, right, depth + 1, true))
+                } else {
+                    (Self::build_sah(boxes, left, depth + 1, false), Self::build_sah(boxes, right, depth + 1, false))
+                };
+                BuildNode::Node(bbox, axis, Box::new(left), Box::new(right))
+            },
+            None => BuildNode::Leaf(bbox, indices),
+        }
+    }
+
+    /// Best partition of the primitives and its axis, or None if they should stay together in a leaf
+    fn sah_split(boxes: &[AABB], indices: &[usize], bbox: &AABB, parallel: bool) -> Option<(usize, Vec<usize>, Vec<usize>)> {
+        if indices.len() <= 1 {
+            return None;
+        }
+
+        let centroids = Self::bounds(indices, parallel, |i| AABB::from_points(boxes[i].centroid(), boxes[i].centroid()));
+        let bin_of = |i: usize, axis: usize| {
+            let extent = centroids.axis(axis);
+            let offset = (boxes[i].centroid().axis(axis) - extent.min) / extent.size();
+            ((offset * SAH_BINS as f64) as usize).min(SAH_BINS - 1)
+        };
+
+        // (cost, axis, first bin of the right side)
+        let mut best: Option<(f64, usize, usize)> = None;
+        for axis in 0..3 {
+            if centroids.axis(axis).size() <= 0. {
+                continue;
+            }
+
+            let binning = |indices: &[usize]| {
+                let mut counts = [0usize; SAH_BINS];
+                let mut bin_boxes = [AABB::empty(); SAH_BINS];
+                for &i in indices {
+                    let bin = bin_of(i, axis);
+                    counts[bin] += 1;
+                    bin_boxes[bin] = bin_boxes[bin].surrounding_box(&boxes[i]);
+                }
+                (counts, bin_boxes)
+            };
+            let (counts, bin_boxes) = if parallel && indices.len() >= PARALLEL_BINNING_SIZE {
+                indices.par_chunks(BINNING_CHUNK_SIZE)
+                    .map(binning)
+                    .reduce(|| ([0; SAH_BINS], [AABB::empty(); SAH_BINS]), |(mut counts, mut bin_boxes), (other_counts, other_boxes)| {
+                        for bin in 0..SAH_BINS {
+                            counts[bin] += other_counts[bin];
+                            bin_boxes[bin] = bin_boxes[bin].surrounding_box(&other_boxes[bin]);
+                        }
+                        (counts, bin_boxes)
+                    })
+            } else {
+                binning(indices)
+            };
+
+            // Sweep from the right to get the cost term of every right side
+            let mut right_costs = [0.; SAH_BINS];
+            let (mut right_box, mut right_count) = (AABB::empty(), 0);
+            for bin in (1..SAH_BINS).rev() {
+                right_box = right_box.surrounding_box(&bin_boxes[bin]);
+                right_count += counts[bin];
+                right_costs[bin] = if right_count > 0 { right_box.surface_area() * right_count as f64 } else { 0. };
+            }
+
+            let (mut left_box, mut left_count) = (AABB::empty(), 0);
+            for split in 1..SAH_BINS {
+                left_box = left_box.surrounding_box(&bin_boxes[split - 1]);
+                left_count += counts[split - 1];
+                if left_count == 0 || left_count == indices.len() {
+                    continue;
+                }
+
+                let cost = SAH_TRAVERSAL_COST + (left_box.surface_area() * left_count as f64 + right_costs[split]) / bbox.surface_area();
+                if best.map_or(true, |(best_cost, _, _)| cost < best_cost) {
+                    best = Some((cost, axis, split));
+                }
+            }
+        }
+
+        match best {
+            Some((cost, axis, split)) => {
+                if indices.len() <= MAX_LEAF_SIZE && cost >= indices.len() as f64 {
+                    return None;
+                }
+                let (left, right) = if parallel {
+                    indices.par_iter().partition(|&&i| bin_of(i, axis) < split)
+                } else {
+                    indices.iter().partition(|&&i| bin_of(i, axis) < split)
+                };
+                Some((axis, left, right))
+            },
+            // All the centroids are at the same place, only the count can be reduced
+            None if indices.len() > MAX_LEAF_SIZE => {
+                let mid = indices.len() / 2;
+                Some((0, indices[..mid].to_vec(), indices[mid..].to_vec()))
+            },
+            None => None,
+        }
+    }
+
+    /// Halves the primitives along the largest axis of their centroids
+    fn balanced_split(boxes: &[AABB], indices: &[usize], parallel: bool) -> Option<(usize, Vec<usize>, Vec<usize>)> {
+        if indices.len() <= MAX_LEAF_SIZE {
+            return None;
+        }
+
+        let centroids = Self::bounds(indices, parallel, |i| AABB::from_points(boxes[i].centroid(), boxes[i].centroid()));
+        let axis = (0..3).fold(0, |best, axis| if centroids.axis(axis).size() > centroids.axis(best).size() { axis } else { best });
+
+        // Both sorts are stable
+        let mut indices = indices.to_vec();
+        let compare = |&a: &usize, &b: &usize| boxes[a].centroid().axis(axis).total_cmp(&boxes[b].centroid().axis(axis));
+        if parallel {
+            indices.par_sort_by(compare);
+        } else {
+            indices.sort_by(compare);
+        }
+        let right = indices.split_off(indices.len() / 2);
+        Some((axis, indices, right))
+    }
+
+    /// Union of the boxes of the primitives
+    fn bounds(indices: &[usize], parallel: bool, bbox: impl Fn(usize) -> AABB + Sync) -> AABB {
+        if parallel {
+            indices.par_iter()
+                .map(|&i| bbox(i))
+                .reduce(AABB::empty, |bounds, other| bounds.surrounding_box(&other))
+        } else {
+            indices.iter().fold(AABB::empty(), |bounds, &i| bounds.surrounding_box(&bbox(i)))
+        }
+    }
+
+    fn box_compare(box1: &Box<dyn Hittable>, box2: &Box<dyn Hittable>, axis: usize) -> std::cmp::Ordering {
+        let box1 = box1.bounding_box();
+        let box2 = box2.bounding_box();
+
+        box1.axis(axis).min.partial_cmp(&box2.axis(axis).min).unwrap()
+    }
+
+    /// Lays the nodes out in depth-first order, and the primitives in the order of the leaves
+    fn flatten(root: BuildNode, objects: &[Box<dyn Hittable>]) -> Self {
+        fn visit(node: BuildNode, nodes: &mut Vec<LinearNode>, primitives: &mut Vec<usize>) {
+            match node {
+                BuildNode::Leaf(bbox, indices) => {
+                    nodes.push(LinearNode { bbox, offset: primitives.len() as u32, count: indices.len() as u16, axis: 0 });
+                    primitives.extend(indices);
+                },
+                BuildNode::Node(bbox, axis, left, right) => {
+                    let index = nodes.len();
+                    nodes.push(LinearNode { bbox, offset: 0, count: 0, axis: axis as u8 });
+                    visit(*left, nodes, primitives);
+                    nodes[index].offset = nodes.len() as u32;
+                    visit(*right, nodes, primitives);
+                },
+            }
+        }
+
+        let mut nodes = Vec::new();
+        let mut indices = Vec::new();
+        visit(root, &mut nodes, &mut indices);
+
+        let mut tree = Self {
+            wide_nodes: wide::collapse(&nodes).into(),
+            nodes: nodes.into(),
+            primitives: indices.iter().map(|&i| objects[i].clone()).collect(),
+            indices: indices.into(),
+            build_cost: 0.,
+        };
+        tree.build_cost = tree.sah_cost();
+        tree
+    }
+
+    /// Updates the bounding boxes bottom-up after the objects moved, keeping the structure of the tree.
+    /// `list` must hold the same objects, in the same order, as the one the tree was built from.
+    pub fn refit(&mut self, list: &HittableList) {
+        let objects = list.objects();
+        let objects = objects.borrow();
+        assert!(self.indices.iter().all(|&i| i < objects.len()), "Cannot refit BVH tree with fewer objects");
+
+        self.primitives = self.indices.iter().map(|&i| objects[i].clone()).collect();
+
+        // Children always come after their parent, so a reverse walk visits them first
+        let mut nodes = self.nodes.to_vec();
+        for index in (0..nodes.len()).rev() {
+            let node = nodes[index];
+            nodes[index].bbox = if node.count > 0 {
+                let first = node.offset as usize;
+                self.primitives[first..first + node.count as usize].iter()
+                    .fold(AABB::empty(), |bbox, primitive| bbox.surrounding_box(&primitive.bounding_box()))
+            } else {
+                nodes[index + 1].bbox.surrounding_box(&nodes[node.offset as usize].bbox)
+            };
+        }
+        self.wide_nodes = wide::collapse(&nodes).into();
+        self.nodes = nodes.into();
+    }
+
+    /// Expected cost of tracing a ray through the tree according to the SAH,
+    /// relative to the cost of intersecting a primitive
+    pub fn sah_cost(&self) -> f64 {
+        let root_area = self.nodes[0].bbox.surface_area();
+        if root_area <= 0. {
+            return self.primitives.len() as f64;
+        }
+
+        self.nodes.iter().map(|node| {
+            let probability = node.bbox.surface_area() / root_area;
+            if node.count > 0 { probability * node.count as f64 } else { probability * SAH_TRAVERSAL_COST }
+        }).sum()
+    }
+
+    /// Whether refitting degraded the tree enough that rebuilding it would pay off,
+    /// its SAH cost having grown more than `threshold` times the cost it had once built (1.5 is a good start)
+    pub fn needs_rebuild(&self, threshold: f64) -> bool {
+        self.sah_cost() > threshold * self.build_cost
+    }
+
+    /// Traversal of the binary nodes, one box at a time (for comparisons with the 4-wide one)
+    pub fn hit_binary(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let direction_is_negative = [ray.direction().x() < 0., ray.direction().y() < 0., ray.direction().z() < 0.];
+
+        let mut hit_record = None;
+        let mut closest = ray_t.max;
+
+        let mut stack = [0usize; MAX_DEPTH];
+        let mut stack_size = 0;
+        let mut current = 0;
+        loop {
+            let node = &self.nodes[current];
+            statistics::count_node_visits(1);
+            if node.bbox.hit(ray, &Interval::new(ray_t.min, closest)) {
+                if node.count > 0 {
+                    let first = node.offset as usize;
+                    statistics::count_primitive_tests(node.count as usize);
+                    for primitive in &self.primitives[first..first + node.count as usize] {
+                        if let Some(hit) = primitive.hit(ray, &Interval::new(ray_t.min, closest)) {
+                            closest = hit.t;
+                            hit_record = Some(hit);
+                        }
+                    }
+                } else {
+                    // Visit the child on the side the ray comes from first, keep the other one for later
+                    let (near, far) = if direction_is_negative[node.axis as usize] {
+                        (node.offset as usize, current + 1)
+                    } else {
+                        (current + 1, node.offset as usize)
+                    };
+                    stack[stack_size] = far;
+                    stack_size += 1;
+                    current = near;
+                    continue;
+                }
+            }
+
+            if stack_size == 0 {
+                break;
+            }
+            stack_size -= 1;
+            current = stack[stack_size];
+        }
+
+        hit_record
+    }
+
+    /// Packet traversal of the binary nodes. Each node is first tested against the frustum of the packet,
+    /// then against the rays of the mask until one of them hits it, its children being visited with the
+    /// remaining rays. Leaves get the exact mask of the rays hitting them, whose search is bounded by `closest`.
+    /// `intersect_leaf` returns the rays that are done, which leave the traversal.
+    fn traverse_packet(&self, packet: &RayPacket, active: u16, ray_t: &Interval, mut intersect_leaf: impl FnMut(&[Box<dyn Hittable>], u16) -> u16, closest: impl Fn(usize) -> f64) {
+        let mut stack = [(0usize, 0u16); MAX_DEPTH];
+        let mut stack_size = 0;
+        let mut current = (0, active);
+        let mut active = active;
+        loop {
+            let (index, mask) = current;
+            let mask = mask & active;
+            let node = &self.nodes[index];
+            statistics::count_node_visits(1);
+
+            let mut hit_mask = 0;
+            if mask != 0 {
+                let farthest = packet.active(mask).map(|(i, _)| closest(i)).fold(ray_t.min, f64::max);
+                if packet.may_hit(&node.bbox, &Interval::new(ray_t.min, farthest)) {
+                    // Above the leaves, the rays following the first one hitting the box go on untested
+                    for (i, ray) in packet.active(mask) {
+                        if node.bbox.hit(ray, &Interval::new(ray_t.min, closest(i))) {
+                            if node.count == 0 {
+                                hit_mask = mask & !((1 << i) - 1);
+                                break;
+                            }
+                            hit_mask |= 1 << i;
+                        }
+                    }
+                }
+            }
+
+            if hit_mask != 0 {
+                if node.count > 0 {
+                    let first = node.offset as usize;
+                    statistics::count_primitive_tests(node.count as usize * hit_mask.count_ones() as usize);
+                    active &= !intersect_leaf(&self.primitives[first..first + node.count as usize], hit_mask);
+                } else {
+                    // The rays of a coherent packet all come from the same side of the split
+                    let (near, far) = if packet.direction_is_negative(node.axis as usize) {
+                        (node.offset as usize, index + 1)
+                    } else {
+                        (index + 1, node.offset as usize)
+                    };
+                    stack[stack_size] = (far, hit_mask);
+                    stack_size += 1;
+                    current = (near, hit_mask);
+                    continue;
+                }
+            }
+
+            if stack_size == 0 || active == 0 {
+                break;
+            }
+            stack_size -= 1;
+            current = stack[stack_size];
+        }
+    }
+
+    /// Number of nodes and depth of the tree
+    pub fn statistics(&self) -> (usize, usize) {
+        fn depth(nodes: &[LinearNode], index: usize) -> usize {
+            let node = &nodes[index];
+            if node.count > 0 {
+                1
+            } else {
+                1 + depth(nodes, index + 1).max(depth(nodes, node.offset as usize))
+            }
+        }
+
+        (self.nodes.len(), depth(&self.nodes, 0))
+    }
+}
+
+impl BuildNode {
+    fn bounding_box(&self) -> AABB {
+        match self {
+            BuildNode::Leaf(bbox, _) | BuildNode::Node(bbox, _, _, _) => *bbox,
+        }
+    }
+}
+
+/// What the 4-wide traversal does on every node visited and primitive tested, so the debug
+/// views can count them without slowing down the normal traversal
+trait Visitor {
+    fn visit_node(&mut self);
+    fn test_primitive(&mut self, primitive: &dyn Hittable, ray: &Ray, ray_t: &Interval) -> Option<HitRecord>;
+}
+
+struct Closest;
+
+impl Visitor for Closest {
+    #[inline(always)]
+    fn visit_node(&mut self) {
+        statistics::count_node_visits(1);
+    }
+
+    #[inline(always)]
+    fn test_primitive(&mut self, primitive: &dyn Hittable, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        primitive.hit(ray, ray_t)
+    }
+}
+
+impl Visitor for HitDiagnostics {
+    fn visit_node(&mut self) {
+        self.node_visits += 1;
+    }
+
+    fn test_primitive(&mut self, primitive: &dyn Hittable, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        primitive.hit_diagnostics(ray, ray_t, self)
+    }
+}
+
+impl BvhTree {
+    /// Picks the ray-box test once per ray rather than once per node, so that it gets inlined
+    #[inline(always)]
+    fn traverse<V: Visitor>(&self, ray: &Ray, ray_t: &Interval, visitor: &mut V) -> Option<HitRecord> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx") {
+                // Safety: AVX support was just checked
+                return unsafe { self.traverse_avx(ray, ray_t, visitor) };
+            }
+        }
+
+        self.traverse_with(ray, ray_t, visitor, WideNode::hit_scalar)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx")]
+    unsafe fn traverse_avx<V: Visitor>(&self, ray: &Ray, ray_t: &Interval, visitor: &mut V) -> Option<HitRecord> {
+        // Safety: the caller checked the AVX support
+        self.traverse_with(ray, ray_t, visitor, |node, ray, t_min, t_max| unsafe { node.hit_avx(ray, t_min, t_max) })
+    }
+
+    #[inline(always)]
+    fn traverse_with<V: Visitor>(&self, ray: &Ray, ray_t: &Interval, visitor: &mut V, hit_boxes: impl Fn(&WideNode, &Ray, f64, f64) -> [f64; 4]) -> Option<HitRecord> {
+        let mut hit_record = None;
+        let mut closest = ray_t.max;
+
+        // Inner nodes waiting to be visited, with the distance at which the ray enters them
+        // Left uninitialized: zeroing it took a noticeable part of the short traversals
+        let mut stack = [MaybeUninit::<(u32, f64)>::uninit(); 3 * MAX_DEPTH];
+        let mut stack_size = 0;
+        let mut current = Some(0);
+        while let Some(index) = current {
+            let node = &self.wide_nodes[index as usize];
+            visitor.visit_node();
+            let entries = hit_boxes(node, ray, ray_t.min, closest);
+
+            // Children from the nearest to the farthest
+            let order = nearest_first(&entries);
+
+            // Intersect the leaves right away, and keep the inner nodes for later, the nearest on top
+            let mut inner = [0usize; 4];
+            let mut inner_count = 0;
+            for lane in order {
+                // A missed box enters at infinity, which isn't beyond an unbounded ray
+                if node.children[lane] == EMPTY || entries[lane] == f64::INFINITY || entries[lane] > closest {
+                    continue;
+                }
+                if node.counts[lane] > 0 {
+                    let first = node.children[lane] as usize;
+                    statistics::count_primitive_tests(node.counts[lane] as usize);
+                    for primitive in &self.primitives[first..first + node.counts[lane] as usize] {
+                        if let Some(hit) = visitor.test_primitive(primitive.as_ref(), ray, &Interval::new(ray_t.min, closest)) {
+                            closest = hit.t;
+                            hit_record = Some(hit);
+                        }
+                    }
+                } else {
+                    inner[inner_count] = lane;
+                    inner_count += 1;
+                }
+            }
+            for &lane in inner[..inner_count].iter().rev() {
+                stack[stack_size].write((node.children[lane], entries[lane]));
+                stack_size += 1;
+            }
+
+            current = None;
+            while stack_size > 0 {
+                stack_size -= 1;
+                // Safety: the entries below stack_size were all written
+                let (index, entry) = unsafe { stack[stack_size].assume_init() };
+                if entry <= closest {
+                    current = Some(index);
+                    break;
+                }
+            }
+        }
+
+        hit_record
+    }
+
+}
+
+/// Lanes sorted by their entry distances, with a sorting network
+#[inline(always)]
+fn nearest_first(entries: &[f64; 4]) -> [usize; 4] {
+    let mut order = [0, 1, 2, 3];
+    for (a, b) in [(0, 1), (2, 3), (0, 2), (1, 3), (1, 2)] {
+        if entries[order[b]] < entries[order[a]] {
+            order.swap(a, b);
+        }
+    }
+    order
+}
+
+impl Hittable for BvhTree {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        self.traverse(ray, ray_t, &mut Closest)
+    }
+
+    fn hit_diagnostics(&self, ray: &Ray, ray_t: &Interval, diagnostics: &mut HitDiagnostics) -> Option<HitRecord> {
+        self.traverse(ray, ray_t, diagnostics)
+    }
+
+    /// Incoherent and small packets are traced one ray after the other, through the 4-wide nodes
+    fn hit_packet(&self, packet: &RayPacket, active: u16, ray_t: &Interval, hits: &mut [Option<HitRecord>]) {
+        if !packet.is_coherent() || active.count_ones() < MIN_PACKET_RAYS {
+            for (i, ray) in packet.active(active) {
+                let closest = hits[i].as_ref().map_or(ray_t.max, |hit| hit.t);
+                if let Some(hit_record) = self.hit(ray, &Interval::new(ray_t.min, closest)) {
+                    hits[i] = Some(hit_record);
+                }
+            }
+            return;
+        }
+
+        // The hits are both updated by the leaves and read by the traversal
+        let hits = RefCell::new(hits);
+        self.traverse_packet(packet, active, ray_t, |primitives, mask| {
+            for primitive in primitives {
+                primitive.hit_packet(packet, mask, ray_t, &mut hits.borrow_mut());
+            }
+            0
+        }, |i| hits.borrow()[i].as_ref().map_or(ray_t.max, |hit| hit.t));
+    }
+
+    /// Shadow rays leave the traversal as soon as they are occluded
+    fn occluded_packet(&self, packet: &RayPacket, active: u16, ray_t: &Interval) -> u16 {
+        if !packet.is_coherent() || active.count_ones() < MIN_PACKET_RAYS {
+            return packet.active(active)
+                .filter(|(_, ray)| self.hit(ray, ray_t).is_some())
+                .fold(0, |occluded, (i, _)| occluded | 1 << i);
+        }
+
+        let mut occluded = 0;
+        self.traverse_packet(packet, active, ray_t, |primitives, mask| {
+            let mut leaf_occluded = 0;
+            for primitive in primitives {
+                leaf_occluded |= primitive.occluded_packet(packet, mask & !leaf_occluded, ray_t);
+                if leaf_occluded == mask {
+                    break;
+                }
+            }
+            occluded |= leaf_occluded;
+            leaf_occluded
+        }, |_| ray_t.max);
+        occluded
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.nodes[0].bbox
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Sphere;
+    use crate::material::Lambertian;
+    use crate::color::Color;
+    use crate::vector::{Point, Vec3};
+
+    fn spheres() -> HittableList {
+        spheres_moved(|center| center)
+    }
+
+    fn spheres_moved(motion: impl Fn(Point) -> Point) -> HittableList {
+        let material = Lambertian::new(Color::white());
+        let mut list = HittableList::new();
+        for i in 0..10 {
+            for j in 0..10 {
+                let center = motion(Point::new(i as f64 * 3., ((i * 7 + j * 3) % 5) as f64, j as f64 * 3.));
+                list += Sphere::new(center, 1. + (i + j) as f64 * 0.02, material.clone());
+            }
+        }
+        list
+    }
+
+    #[test]
+    fn test_trees_match_list() {
+        let list = spheres();
+        let trees = [BvhTree::from_list(&list), BvhTree::from_list_median(&list)];
+
+        // Rays going along every direction sign, so both children orders get traversed
+        let directions = [Vec3::new(0.3, -1., 0.2), Vec3::new(-0.3, -1., -0.2), Vec3::new(1., -0.1, 0.), Vec3::new(-1., 0.1, -0.5)];
+        for direction in directions {
+            for k in 0..200 {
+                let origin = Point::new(-5. + (k % 20) as f64 * 2., 20., -5. + (k / 20) as f64 * 3.5) - direction * 10.;
+                let ray = Ray::new(origin, direction, 0.);
+                let expected = list.hit(&ray, &Interval::positive()).map(|hit| hit.t);
+                for tree in &trees {
+                    assert_eq!(expected, tree.hit(&ray, &Interval::positive()).map(|hit| hit.t));
+                    assert_eq!(expected, tree.hit_binary(&ray, &Interval::positive()).map(|hit| hit.t));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_packets_match_single_rays() {
+        let list = spheres();
+        let tree = BvhTree::from_list(&list);
+
+        // A pinhole camera looking down at the spheres, and shadow rays towards a point light
+        let eye = Point::new(-10., 30., -20.);
+        let light = Point::new(60., 40., 60.);
+        let mut rays = Vec::new();
+        for j in 0..16 {
+            for i in 0..16 {
+                let target = Point::new(-2. + i as f64 * 2., 0., -2. + j as f64 * 2.);
+                rays.push(Ray::new(eye, target - eye, 0.));
+            }
+        }
+        // Diffuse bounces, whose directions go everywhere
+        let bounces: Vec<Ray> = rays.iter().enumerate()
+            .map(|(k, ray)| Ray::new(ray.at(0.9), Vec3::new((k % 3) as f64 - 1., (k % 5) as f64 - 2., (k % 7) as f64 - 3.), 0.))
+            .collect();
+
+        for (rays, coherent) in [(&rays, true), (&bounces, false)] {
+            for chunk in rays.chunks(16) {
+                let packet = RayPacket::new(chunk);
+                assert_eq!(coherent, packet.is_coherent());
+                let mut hits = vec![None; chunk.len()];
+                tree.hit_packet(&packet, packet.all(), &Interval::positive(), &mut hits);
+
+                let mut shadow_rays = Vec::new();
+                for (ray, hit) in chunk.iter().zip(&hits) {
+                    let expected = list.hit(ray, &Interval::positive());
+                    assert_eq!(expected.as_ref().map(|hit| hit.t), hit.as_ref().map(|hit| hit.t));
+                    let origin = expected.map_or(ray.at(1.), |hit| hit.point);
+                    shadow_rays.push(Ray::new(origin, light - origin, 0.));
+                }
+
+                let shadow_packet = RayPacket::new(&shadow_rays);
+                assert!(shadow_packet.is_coherent());
+                let occluded = tree.occluded_packet(&shadow_packet, shadow_packet.all(), &Interval::new(0.001, 1.));
+                for (i, ray) in shadow_rays.iter().enumerate() {
+                    let expected = list.hit(ray, &Interval::new(0.001, 1.)).is_some();
+                    assert_eq!(expected, occluded & (1 << i) != 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_diagnostics_match_hits() {
+        let list = spheres();
+        let tree = BvhTree::from_list(&list);
+
+        let eye = Point::new(-10., 30., -20.);
+        for i in 0..32 {
+            let ray = Ray::new(eye, Point::new(-2. + i as f64, 0., 10.) - eye, 0.);
+            let mut diagnostics = HitDiagnostics::new();
+            let hit = tree.hit_diagnostics(&ray, &Interval::positive(), &mut diagnostics);
+            assert_eq!(tree.hit(&ray, &Interval::positive()).map(|hit| hit.t), hit.map(|hit| hit.t));
+            assert!(diagnostics.node_visits > 0);
+            assert!(diagnostics.primitive_tests < list.objects().borrow().len());
+        }
+    }
+
+    #[test]
+    fn test_sah_deterministic() {
+        let list = spheres();
+        let (nodes, depth) = BvhTree::from_list(&list).statistics();
+        for _ in 0..5 {
+            assert_eq!(BvhTree::from_list(&list).statistics(), (nodes, depth));
+        }
+        assert!(depth < 16);
+
+        let indices = (0..list.objects().borrow().len()).collect();
+        assert_eq!(BvhTree::from_objects(list.objects().clone(), indices).indices, BvhTree::from_list(&list).indices);
+    }
+
+    #[test]
+    fn test_parallel_build_matches_sequential() {
+        // Enough primitives for the parallel binning, and a pile of identical spheres split by count
+        let material = Lambertian::new(Color::white());
+        let mut list = HittableList::new();
+        for k in 0..PARALLEL_BINNING_SIZE + 5000 {
+            let center = Point::new((k % 211) as f64, ((k * 7) % 101) as f64, (k / 211) as f64 * 0.5);
+            list += Sphere::new(center, 0.3, material.clone());
+        }
+        for _ in 0..2 * PARALLEL_BUILD_SIZE {
+            list += Sphere::new(Point::new(-10., -10., -10.), 0.3, material.clone());
+        }
+
+        let sequential = BvhTree::from_list_sequential(&list);
+        let parallel = BvhTree::from_list(&list);
+        assert_eq!(sequential.indices, parallel.indices);
+        assert_eq!(sequential.nodes.len(), parallel.nodes.len());
+        for (a, b) in sequential.nodes.iter().zip(parallel.nodes.iter()) {
+            assert_eq!((a.offset, a.count, a.axis), (b.offset, b.count, b.axis));
+            assert_eq!(a.bbox.surface_area().to_bits(), b.bbox.surface_area().to_bits());
+        }
+    }
+
+    #[test]
+    fn test_refit() {
+        let mut tree = BvhTree::from_list(&spheres());
+        assert!(!tree.needs_rebuild(1.5));
+
+        // Small motion: the refitted tree finds the new positions and stays good
+        let moved = spheres_moved(|center| center + Vec3::new(0.5, 1., 0.));
+        tree.refit(&moved);
+        for k in 0..200 {
+            let origin = Point::new(-5. + (k % 20) as f64 * 2., 20., -5. + (k / 20) as f64 * 3.5);
+            let ray = Ray::new(origin, Vec3::new(0.3, -1., 0.2), 0.);
+            let expected = moved.hit(&ray, &Interval::positive()).map(|hit| hit.t);
+            assert_eq!(expected, tree.hit(&ray, &Interval::positive()).map(|hit| hit.t));
+        }
+        assert!(!tree.needs_rebuild(1.5));
+
+        // Shuffling the spheres over the grid makes every subtree span the whole scene
+        let shuffled = spheres_moved(|center| {
+            let (i, j) = ((center.x() / 3.).round() as usize, (center.z() / 3.).round() as usize);
+            Point::new(((i * 7 + 3) % 10) as f64 * 3., center.y(), ((j * 3 + i) % 10) as f64 * 3.)
+        });
+        tree.refit(&shuffled);
+        assert!(tree.needs_rebuild(1.5));
+        assert!(!BvhTree::from_list(&shuffled).needs_rebuild(1.5));
+    }
+}
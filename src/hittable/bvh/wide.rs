@@ -0,0 +1,182 @@
+use crate::ray::Ray;
+use crate::hittable::AABB;
+use crate::hittable::bvh::LinearNode;
+
+/// Child slot of a wide node left unused
+pub(super) const EMPTY: u32 = u32::MAX;
+
+/// Node of the 4-wide tree, collapsed from the binary one. The boxes of the children are stored
+/// axis by axis so they can be tested against a ray at once. Leaves have a primitive `count` and
+/// `children` is then the index of their first primitive, inner nodes have a zero count.
+/// Missing children are marked as `EMPTY`: an empty box isn't enough, the slab test swapping
+/// its inverted bounds along negative directions.
+#[derive(Clone, Copy)]
+pub(super) struct WideNode {
+    min: [[f64; 4]; 3],
+    max: [[f64; 4]; 3],
+    pub children: [u32; 4],
+    pub counts: [u16; 4],
+}
+
+impl WideNode {
+    fn empty() -> Self {
+        Self {
+            min: [[f64::INFINITY; 4]; 3],
+            max: [[-f64::INFINITY; 4]; 3],
+            children: [EMPTY; 4],
+            counts: [0; 4],
+        }
+    }
+
+    fn set_box(&mut self, lane: usize, bbox: &AABB) {
+        for axis in 0..3 {
+            self.min[axis][lane] = bbox.axis(axis).min;
+            self.max[axis][lane] = bbox.axis(axis).max;
+        }
+    }
+
+    /// Distance at which the ray enters each of the 4 boxes, or infinity if it misses it.
+    /// The traversal picks the implementation once per ray instead.
+    #[cfg(test)]
+    pub fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> [f64; 4] {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx") {
+                // Safety: AVX support was just checked
+                return unsafe { self.hit_avx(ray, t_min, t_max) };
+            }
+        }
+
+        self.hit_scalar(ray, t_min, t_max)
+    }
+
+    /// Same slab test as `AABB::hit_interval`, one box after the other
+    pub fn hit_scalar(&self, ray: &Ray, t_min: f64, t_max: f64) -> [f64; 4] {
+        let mut entries = [f64::INFINITY; 4];
+        for (lane, entry) in entries.iter_mut().enumerate() {
+            let mut tmin = t_min;
+            let mut tmax = t_max;
+            for axis in 0..3 {
+                let t1 = (self.min[axis][lane] - ray.origin().axis(axis)) * ray.inv_direction().axis(axis);
+                let t2 = (self.max[axis][lane] - ray.origin().axis(axis)) * ray.inv_direction().axis(axis);
+
+                tmin = f64::min(f64::max(t1, tmin), f64::max(t2, tmin));
+                tmax = f64::max(f64::min(t1, tmax), f64::min(t2, tmax));
+            }
+
+            if tmin <= tmax {
+                *entry = tmin;
+            }
+        }
+        entries
+    }
+
+    /// The slab test of the 4 boxes in AVX registers. The NaN produced by a box face containing
+    /// the origin along an axis parallel to the ray is in the first operand of min/max, which then
+    /// return the second one, exactly like `f64::min` and `f64::max` in the scalar version.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx")]
+    pub unsafe fn hit_avx(&self, ray: &Ray, t_min: f64, t_max: f64) -> [f64; 4] {
+        use std::arch::x86_64::*;
+
+        let mut tmin = _mm256_set1_pd(t_min);
+        let mut tmax = _mm256_set1_pd(t_max);
+        for axis in 0..3 {
+            let origin = _mm256_set1_pd(ray.origin().axis(axis));
+            let inv_direction = _mm256_set1_pd(ray.inv_direction().axis(axis));
+            let t1 = _mm256_mul_pd(_mm256_sub_pd(_mm256_loadu_pd(self.min[axis].as_ptr()), origin), inv_direction);
+            let t2 = _mm256_mul_pd(_mm256_sub_pd(_mm256_loadu_pd(self.max[axis].as_ptr()), origin), inv_direction);
+
+            let new_tmin = _mm256_min_pd(_mm256_max_pd(t1, tmin), _mm256_max_pd(t2, tmin));
+            tmax = _mm256_max_pd(_mm256_min_pd(t1, tmax), _mm256_min_pd(t2, tmax));
+            tmin = new_tmin;
+        }
+
+        let hit = _mm256_cmp_pd::<_CMP_LE_OQ>(tmin, tmax);
+        let entries = _mm256_blendv_pd(_mm256_set1_pd(f64::INFINITY), tmin, hit);
+
+        let mut result = [0.; 4];
+        _mm256_storeu_pd(result.as_mut_ptr(), entries);
+        result
+    }
+}
+
+/// Collapses the binary tree into a 4-wide one, in depth-first order: every node pulls up the
+/// children of its largest inner children until it has four of them.
+pub(super) fn collapse(nodes: &[LinearNode]) -> Vec<WideNode> {
+    fn visit(nodes: &[LinearNode], index: usize, wide: &mut Vec<WideNode>) -> u32 {
+        let node = &nodes[index];
+        let mut children = if node.count > 0 { vec![index] } else { vec![index + 1, node.offset as usize] };
+        while children.len() < 4 {
+            let largest = children.iter()
+                .enumerate()
+                .filter(|(_, &child)| nodes[child].count == 0)
+                .max_by(|(_, &a), (_, &b)| nodes[a].bbox.surface_area().total_cmp(&nodes[b].bbox.surface_area()));
+            match largest {
+                Some((k, &child)) => {
+                    children.remove(k);
+                    children.push(child + 1);
+                    children.push(nodes[child].offset as usize);
+                },
+                None => break,
+            }
+        }
+
+        let position = wide.len();
+        wide.push(WideNode::empty());
+        for (lane, &child) in children.iter().enumerate() {
+            let child = &nodes[child];
+            wide[position].set_box(lane, &child.bbox);
+            wide[position].counts[lane] = child.count;
+            wide[position].children[lane] = if child.count > 0 { child.offset } else { 0 };
+        }
+        for (lane, &child) in children.iter().enumerate() {
+            if nodes[child].count == 0 {
+                wide[position].children[lane] = visit(nodes, child, wide);
+            }
+        }
+
+        position as u32
+    }
+
+    let mut wide = Vec::new();
+    visit(nodes, 0, &mut wide);
+    wide
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interval::Interval;
+    use crate::vector::{Point, Vec3};
+
+    #[test]
+    fn test_simd_matches_scalar() {
+        let boxes = [
+            AABB::from_points(Point::new(0., 0., 0.), Point::new(1., 1., 1.)),
+            AABB::from_points(Point::new(-2., 0., 3.), Point::new(-1., 2., 4.)),
+            AABB::from_points(Point::new(0., -1., 0.), Point::new(0., 1., 5.)),
+            AABB::from_points(Point::new(3., 3., 3.), Point::new(4., 4., 4.)),
+        ];
+        let mut node = WideNode::empty();
+        for (lane, bbox) in boxes.iter().enumerate() {
+            node.set_box(lane, bbox);
+        }
+
+        // Rays along and across the box faces included, for the infinite and NaN cases
+        let origins = [Point::new(0.5, 0.5, -3.), Point::new(0., 0., -1.), Point::new(-1.5, 5., 3.5), Point::new(2., 0.5, 0.5)];
+        let directions = [Vec3::new(0., 0., 1.), Vec3::new(0., -1., 0.), Vec3::new(-1., 0.1, 0.3), Vec3::new(0.2, 0.3, 1.)];
+        for origin in origins {
+            for direction in directions {
+                let ray = Ray::new(origin, direction, 0.);
+                let entries = node.hit(&ray, 0.001, 100.);
+                assert_eq!(entries.map(f64::to_bits), node.hit_scalar(&ray, 0.001, 100.).map(f64::to_bits));
+
+                for (lane, bbox) in boxes.iter().enumerate() {
+                    let expected = bbox.hit_interval(&ray, &Interval::new(0.001, 100.)).map_or(f64::INFINITY, |t| t.min);
+                    assert_eq!(entries[lane].to_bits(), expected.to_bits());
+                }
+            }
+        }
+    }
+}
@@ -35,46 +35,43 @@ impl Hittable for ConstantMedium {
         self.boundary.bounding_box()
     }
 
-    fn hit(&self, ray: &Ray, _ray_t: &Interval) -> Option<HitRecord> {
-        let mut hit_record1 = if let Some(hr) = self.boundary.hit(ray, &Interval::universe()) {
-            hr
-        } else {
-            return None;
-        };
-
-        let hit_record2 = if let Some(hr) = self.boundary.hit(ray, &Interval::new(hit_record1.t + 0.0001, f64::INFINITY)) {
-            hr
-        } else {
-            return None;
-        };
-
-        if hit_record1.t >= hit_record2.t {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        // Works with non-convex boundaries too: the ray can go in and out of the medium several times
+        let intervals = self.boundary.hit_intervals(ray);
+        if intervals.is_empty() {
             return None;
         }
 
-        if hit_record1.t < 0. {
-            hit_record1.t = 0.;
-        }
-
         let mut rng = rand::thread_rng();
 
         let ray_length = ray.direction().length();
-        let distance_inside_boundary = (hit_record2.t - hit_record1.t) * ray_length;
-        let hit_distance = self.inv_neg_density * f64::ln(rng.gen_range(0.0..1.0));
+        let mut hit_distance = self.inv_neg_density * f64::ln(rng.gen_range(0.0..1.0));
 
-        if hit_distance > distance_inside_boundary {
-            return None;
+        for interval in intervals {
+            let t_enter = interval.enter.t.max(ray_t.min).max(0.);
+            let t_exit = interval.exit.t.min(ray_t.max);
+            if t_enter >= t_exit {
+                continue;
+            }
+
+            let distance_inside_boundary = (t_exit - t_enter) * ray_length;
+            if hit_distance > distance_inside_boundary {
+                hit_distance -= distance_inside_boundary;
+                continue;
+            }
+
+            let t = t_enter + hit_distance / ray_length;
+            return Some(HitRecord::new(
+                ray.at(t),
+                Vec3::new(1., 0., 0.), // Arbitrary
+                t,
+                Uv { u: 0., v: 0. }, // Arbitrary UV
+                ray,
+                self.phase_function.clone(),
+            ));
         }
 
-        let t = hit_record1.t + hit_distance / ray_length;
-        Some(HitRecord::new(
-            ray.at(t),
-            Vec3::new(1., 0., 0.), // Arbitrary
-            t,
-            Uv { u: 0., v: 0. }, // Arbitrary UV
-            ray,
-            self.phase_function.clone(),
-        ))
+        None
     }
 
     fn box_clone(&self) -> Box<dyn Hittable> {
@@ -0,0 +1,223 @@
+use crate::hittable::{HitRecord, HitInterval, Hittable, AABB};
+use crate::interval::Interval;
+use crate::ray::Ray;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    fn inside(&self, inside_a: bool, inside_b: bool) -> bool {
+        match self {
+            CsgOperation::Union => inside_a || inside_b,
+            CsgOperation::Intersection => inside_a && inside_b,
+            CsgOperation::Difference => inside_a && !inside_b,
+        }
+    }
+}
+
+/// Constructive solid geometry node, combining two closed objects
+#[derive(Clone)]
+pub struct Csg {
+    a: Box<dyn Hittable>,
+    b: Box<dyn Hittable>,
+    operation: CsgOperation,
+    bbox: AABB,
+}
+
+impl Csg {
+    pub fn new(a: Box<dyn Hittable>, b: Box<dyn Hittable>, operation: CsgOperation) -> Box<dyn Hittable> {
+        let bbox = match operation {
+            CsgOperation::Union => a.bounding_box().surrounding_box(&b.bounding_box()),
+            CsgOperation::Intersection | CsgOperation::Difference => a.bounding_box(),
+        };
+
+        Box::new(Self { a, b, operation, bbox })
+    }
+
+    pub fn union(a: Box<dyn Hittable>, b: Box<dyn Hittable>) -> Box<dyn Hittable> {
+        Self::new(a, b, CsgOperation::Union)
+    }
+
+    pub fn intersection(a: Box<dyn Hittable>, b: Box<dyn Hittable>) -> Box<dyn Hittable> {
+        Self::new(a, b, CsgOperation::Intersection)
+    }
+
+    pub fn difference(a: Box<dyn Hittable>, b: Box<dyn Hittable>) -> Box<dyn Hittable> {
+        Self::new(a, b, CsgOperation::Difference)
+    }
+
+    fn oriented(hit_record: HitRecord, ray: &Ray, entering: bool) -> HitRecord {
+        // The boundary normal must face the ray when entering the result, and follow it when leaving
+        let facing_ray = ray.direction().dot(&hit_record.normal) < 0.;
+        let normal = if facing_ray == entering { hit_record.normal } else { -hit_record.normal };
+
+        // Turn the tangent frame inside out along with the surface, so that dpdu x dpdv stays outward
+        let dpdv = if entering == hit_record.front_face { hit_record.dpdv } else { -hit_record.dpdv };
+        HitRecord::new(hit_record.point, normal, hit_record.t, hit_record.uv, ray, hit_record.material)
+            .with_tangent_frame(hit_record.dpdu, dpdv)
+    }
+}
+
+impl Hittable for Csg {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        if !self.bbox.hit(ray, &Interval::universe()) {
+            return None;
+        }
+
+        self.hit_intervals(ray)
+            .into_iter()
+            .flat_map(|interval| [interval.enter, interval.exit])
+            .find(|hit_record| ray_t.surrounds(hit_record.t))
+    }
+
+    fn hit_intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        // Sweep the boundaries of both operands along the ray, and keep those where the result changes
+        let mut events = Vec::new();
+        for (is_a, intervals) in [(true, self.a.hit_intervals(ray)), (false, self.b.hit_intervals(ray))] {
+            for interval in intervals {
+                events.push((is_a, true, interval.enter));
+                events.push((is_a, false, interval.exit));
+            }
+        }
+        events.sort_by(|e1, e2| e1.2.t.partial_cmp(&e2.2.t).unwrap());
+
+        let mut intervals = Vec::new();
+        let mut enter = None;
+        let (mut inside_a, mut inside_b, mut inside) = (false, false, false);
+
+        for (is_a, entering, hit_record) in events {
+            if is_a {
+                inside_a = entering;
+            } else {
+                inside_b = entering;
+            }
+
+            let now_inside = self.operation.inside(inside_a, inside_b);
+            if now_inside == inside {
+                continue;
+            }
+            inside = now_inside;
+
+            let hit_record = Self::oriented(hit_record, ray, inside);
+            if inside {
+                enter = Some(hit_record);
+            } else if let Some(enter_record) = enter.take() {
+                intervals.push(HitInterval::new(enter_record, hit_record));
+            }
+        }
+
+        intervals
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.bbox
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::{Point, Vec3};
+    use crate::hittable::{Sphere, axis_aligned_cuboid};
+    use crate::material::{BumpMap, Lambertian};
+    use crate::texture::{Texture, Uv};
+    use crate::color::Color;
+
+    fn spheres() -> (Box<dyn Hittable>, Box<dyn Hittable>) {
+        let material = Lambertian::new(Color::white());
+        (
+            Sphere::new(Point::new(0., 0., 0.), 1., material.clone()),
+            Sphere::new(Point::new(1., 0., 0.), 1., material),
+        )
+    }
+
+    #[test]
+    fn test_union() {
+        let (a, b) = spheres();
+        let csg = Csg::union(a, b);
+        let ray = Ray::new(Point::new(-5., 0., 0.), Vec3::new(1., 0., 0.), 0.);
+
+        let intervals = csg.hit_intervals(&ray);
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].enter.t - 4.).abs() < 1e-9);
+        assert!((intervals[0].exit.t - 7.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let (a, b) = spheres();
+        let csg = Csg::intersection(a, b);
+        let ray = Ray::new(Point::new(-5., 0., 0.), Vec3::new(1., 0., 0.), 0.);
+
+        let hit = csg.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 5.).abs() < 1e-9);
+        assert!(hit.front_face);
+        assert!((hit.normal - Vec3::new(-1., 0., 0.)).length() < 1e-9);
+    }
+
+    #[test]
+    fn test_difference() {
+        let (a, b) = spheres();
+        let csg = Csg::difference(a, b);
+
+        // Entering a, then leaving through the carved surface of b
+        let ray = Ray::new(Point::new(-5., 0., 0.), Vec3::new(1., 0., 0.), 0.);
+        let intervals = csg.hit_intervals(&ray);
+        assert_eq!(intervals.len(), 1);
+        assert!((intervals[0].exit.t - 5.).abs() < 1e-9);
+        assert!(!intervals[0].exit.front_face);
+
+        // From the other side, entering through the carved surface
+        let ray = Ray::new(Point::new(5., 0., 0.), Vec3::new(-1., 0., 0.), 0.);
+        let hit = csg.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 5.).abs() < 1e-9);
+        assert!(hit.front_face);
+        assert!((hit.normal - Vec3::new(1., 0., 0.)).length() < 1e-9);
+    }
+
+    #[test]
+    fn test_difference_with_cuboid() {
+        let material = Lambertian::new(Color::white());
+        let sphere = Sphere::new(Point::new(0., 0., 0.), 1., material.clone());
+        let cube = Box::new(axis_aligned_cuboid(Point::new(1., 0., 0.), Vec3::new(1., 1., 1.), material));
+        let csg = Csg::difference(sphere, cube);
+
+        let ray = Ray::new(Point::new(5., 0., 0.), Vec3::new(-1., 0., 0.), 0.);
+        let hit = csg.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bumped_difference() {
+        // Height rising along u
+        struct Ramp;
+        impl Texture for Ramp {
+            fn value(&self, uv: &Uv, _p: &Point) -> Color {
+                Color::white() * uv.u
+            }
+        }
+
+        let material = BumpMap::new(Lambertian::new(Color::white()), std::rc::Rc::new(Ramp), 0.5);
+        let a = Sphere::new(Point::new(0., 0., 0.), 1., material.clone());
+        let b = Sphere::new(Point::new(1., 0., 0.), 1., material);
+        let csg = Csg::difference(a, b);
+
+        // Entering through the carved surface of b, whose tangent frame is turned inside out
+        let ray = Ray::new(Point::new(5., 0.1, 0.2), Vec3::new(-1., 0., 0.), 0.);
+        let hit = csg.hit(&ray, &Interval::positive()).unwrap();
+        assert!(hit.front_face);
+        assert!(hit.dpdu.cross(&hit.dpdv).dot(&hit.normal) > 0.);
+
+        let shading_normal = hit.material.shading_normal(&hit);
+        assert!((shading_normal - hit.normal).length() > 1e-3);
+        assert!(shading_normal.dot(&hit.normal) > 0.);
+    }
+}
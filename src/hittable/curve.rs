@@ -0,0 +1,309 @@
+use crate::vector::{Point, Vec3};
+use crate::hittable::{HitRecord, Hittable, HittableList, AABB};
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::texture::Uv;
+
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CurveKind {
+    /// Flat ribbon always facing the ray
+    Flat,
+    /// Round tube, the normal going around the curve across its width
+    Cylinder,
+}
+
+/// Cubic Bézier segment of varying width (hair, fur, grass...)
+#[derive(Clone)]
+pub struct Curve {
+    control_points: [Point; 4],
+    width: (f64, f64),
+    kind: CurveKind,
+    material: Rc<dyn Material>,
+    bbox: AABB,
+}
+
+fn bezier(cp: &[Vec3; 4], u: f64) -> Vec3 {
+    let v = 1. - u;
+    v * v * v * cp[0] + 3. * v * v * u * cp[1] + 3. * v * u * u * cp[2] + u * u * u * cp[3]
+}
+
+fn bezier_derivative(cp: &[Vec3; 4], u: f64) -> Vec3 {
+    let v = 1. - u;
+    3. * v * v * (cp[1] - cp[0]) + 6. * v * u * (cp[2] - cp[1]) + 3. * u * u * (cp[3] - cp[2])
+}
+
+fn bezier_split(cp: &[Vec3; 4]) -> ([Vec3; 4], [Vec3; 4]) {
+    // de Casteljau at u = 0.5
+    let p01 = (cp[0] + cp[1]) / 2.;
+    let p12 = (cp[1] + cp[2]) / 2.;
+    let p23 = (cp[2] + cp[3]) / 2.;
+    let p012 = (p01 + p12) / 2.;
+    let p123 = (p12 + p23) / 2.;
+    let mid = (p012 + p123) / 2.;
+    ([cp[0], p01, p012, mid], [mid, p123, p23, cp[3]])
+}
+
+impl Curve {
+    /// Bézier segment of the given control points, its width going from `width0` to `width1`
+    pub fn new(control_points: [Point; 4], width0: f64, width1: f64, kind: CurveKind, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        let half_width = width0.max(width1) / 2.;
+        let r = Vec3::new(half_width, half_width, half_width);
+        let bbox = control_points.iter()
+            .map(|p| AABB::from_points(*p - r, *p + r))
+            .reduce(|a, b| a.surrounding_box(&b))
+            .unwrap();
+
+        Box::new(Self {
+            control_points,
+            width: (width0, width1),
+            kind,
+            material,
+            bbox,
+        })
+    }
+
+    /// Smooth strand going through all the points (as a Catmull-Rom spline), made of one segment per pair of points
+    pub fn strand(points: &[Point], width0: f64, width1: f64, kind: CurveKind, material: Rc<dyn Material>) -> HittableList {
+        let mut segments = HittableList::new();
+        if points.len() < 2 {
+            return segments;
+        }
+
+        let n = points.len() - 1;
+        let width_at = |i: usize| width0 + (width1 - width0) * i as f64 / n as f64;
+        for i in 0..n {
+            let previous = points[i.saturating_sub(1)];
+            let next = points[(i + 2).min(n)];
+            let control_points = [
+                points[i],
+                points[i] + (points[i + 1] - previous) / 6.,
+                points[i + 1] - (next - points[i]) / 6.,
+                points[i + 1],
+            ];
+            segments += Self::new(control_points, width_at(i), width_at(i + 1), kind, material.clone());
+        }
+
+        segments
+    }
+
+    /// Loads strands from a text file: one strand per line, made of its root and tip widths followed
+    /// by the coordinates of its points (at least two). Empty lines and lines starting with '#' are ignored.
+    pub fn load(path: &str, kind: CurveKind, material: Rc<dyn Material>) -> Result<HittableList, Error> {
+        let content = fs::read_to_string(path)?;
+        let mut strands = HittableList::new();
+
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let values = line.split_whitespace()
+                .map(|value| value.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}:{}: {}", path, line_number + 1, e)))?;
+            if values.len() < 8 || (values.len() - 2) % 3 != 0 {
+                return Err(Error::new(ErrorKind::InvalidData, format!("{}:{}: expected two widths and at least two points", path, line_number + 1)));
+            }
+
+            let points: Vec<Point> = values[2..].chunks(3).map(|c| Point::new(c[0], c[1], c[2])).collect();
+            strands += Self::strand(&points, values[0], values[1], kind, material.clone());
+        }
+
+        Ok(strands)
+    }
+
+    fn width_at(&self, u: f64) -> f64 {
+        self.width.0 + (self.width.1 - self.width.0) * u
+    }
+
+    fn max_depth(cp: &[Vec3; 4], width: f64) -> usize {
+        // Enough subdivisions for the segments to be close to the curve, compared to its width
+        let mut l0: f64 = 0.;
+        for i in 0..2 {
+            let d = cp[i] - 2. * cp[i + 1] + cp[i + 2];
+            l0 = l0.max(d.x().abs()).max(d.y().abs());
+        }
+
+        let epsilon = width * 0.05;
+        if l0 <= 0. || epsilon <= 0. {
+            return 0;
+        }
+        let r0 = (std::f64::consts::SQRT_2 * 6. * l0 / (8. * epsilon)).log2() / 2.;
+        r0.clamp(0., 10.) as usize
+    }
+
+    /// Control points are in the ray space: the ray goes along the z axis, from the origin.
+    /// Returns the distance along the ray and the u coordinate of the closest hit.
+    fn recursive_hit(&self, cp: &[Vec3; 4], u0: f64, u1: f64, depth: usize, z_range: &mut Interval) -> Option<(f64, f64)> {
+        let half_width = self.width_at(u0).max(self.width_at(u1)) / 2.;
+        let (min, max) = cp.iter().fold((cp[0], cp[0]), |(min, max), p| (
+            Vec3::new(min.x().min(p.x()), min.y().min(p.y()), min.z().min(p.z())),
+            Vec3::new(max.x().max(p.x()), max.y().max(p.y()), max.z().max(p.z())),
+        ));
+        if min.x() - half_width > 0. || max.x() + half_width < 0.
+            || min.y() - half_width > 0. || max.y() + half_width < 0.
+            || max.z() + half_width < z_range.min || min.z() - half_width > z_range.max {
+            return None;
+        }
+
+        if depth > 0 {
+            let u_mid = 0.5 * (u0 + u1);
+            let (left, right) = bezier_split(cp);
+            let hit_left = self.recursive_hit(&left, u0, u_mid, depth - 1, z_range);
+            let hit_right = self.recursive_hit(&right, u_mid, u1, depth - 1, z_range);
+            return hit_right.or(hit_left);
+        }
+
+        // The ray must go between the start and end of the segment
+        let edge = (cp[1].y() - cp[0].y()) * -cp[0].y() + cp[0].x() * (cp[0].x() - cp[1].x());
+        if edge < 0. {
+            return None;
+        }
+        let edge = (cp[2].y() - cp[3].y()) * -cp[3].y() + cp[3].x() * (cp[3].x() - cp[2].x());
+        if edge < 0. {
+            return None;
+        }
+
+        // Closest point of the segment to the ray
+        let segment = Vec3::new(cp[3].x() - cp[0].x(), cp[3].y() - cp[0].y(), 0.);
+        let denom = segment.length_squared();
+        if denom == 0. {
+            return None;
+        }
+        let w = (-(cp[0].x() * segment.x() + cp[0].y() * segment.y()) / denom).clamp(0., 1.);
+        let u = u0 + (u1 - u0) * w;
+
+        let hit_width = self.width_at(u);
+        let pc = bezier(cp, w);
+        if pc.x() * pc.x() + pc.y() * pc.y() > hit_width * hit_width / 4. {
+            return None;
+        }
+        if !z_range.surrounds(pc.z()) {
+            return None;
+        }
+
+        z_range.max = pc.z();
+        Some((pc.z(), u))
+    }
+}
+
+impl Hittable for Curve {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        if !self.bbox.hit(ray, ray_t) {
+            return None;
+        }
+
+        // Move to the ray space
+        let direction_length = ray.direction().length();
+        let e3 = *ray.direction() / direction_length;
+        let (e1, e2) = e3.orthonormal_basis();
+        let cp = self.control_points.map(|p| {
+            let q = p - *ray.origin();
+            Vec3::new(q.dot(&e1), q.dot(&e2), q.dot(&e3))
+        });
+
+        let max_depth = Self::max_depth(&cp, self.width.0.max(self.width.1));
+        let mut z_range = Interval::new(ray_t.min * direction_length, ray_t.max * direction_length);
+        let (z, u) = self.recursive_hit(&cp, 0., 1., max_depth, &mut z_range)?;
+
+        // Build the hit record back in the world space
+        let t = z / direction_length;
+        let hit_point = ray.at(t);
+        let dpdu = bezier_derivative(&self.control_points, u);
+        let tangent = dpdu.normalized();
+        let center = bezier(&self.control_points, u);
+
+        let facing = -e3 + tangent * e3.dot(&tangent);
+        let flat_normal = if facing.near_zero() { tangent.orthonormal_basis().0 } else { facing.normalized() };
+        let side = tangent.cross(&flat_normal);
+        let s = ((hit_point - center).dot(&side) / (self.width_at(u) / 2.)).clamp(-1., 1.);
+
+        let normal = match self.kind {
+            CurveKind::Flat => flat_normal,
+            CurveKind::Cylinder => (1. - s * s).sqrt() * flat_normal + s * side,
+        };
+        let uv = Uv { u, v: (s + 1.) / 2. };
+
+        let dpdv = side * self.width_at(u);
+
+        Some(HitRecord::new(hit_point, normal, t, uv, ray, self.material.clone()).with_tangent_frame(dpdu, dpdv))
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.bbox
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::Lambertian;
+    use crate::color::Color;
+
+    fn straight_curve(kind: CurveKind) -> Box<dyn Hittable> {
+        let control_points = [
+            Point::new(-1., 0., 0.),
+            Point::new(-1. / 3., 0., 0.),
+            Point::new(1. / 3., 0., 0.),
+            Point::new(1., 0., 0.),
+        ];
+        Curve::new(control_points, 0.2, 0.2, kind, Lambertian::new(Color::white()))
+    }
+
+    #[test]
+    fn test_hit_flat() {
+        let curve = straight_curve(CurveKind::Flat);
+
+        let ray = Ray::new(Point::new(0.5, 0.05, -5.), Vec3::new(0., 0., 2.), 0.);
+        let hit = curve.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 2.5).abs() < 1e-9);
+        assert!((hit.uv.u - 0.75).abs() < 1e-9);
+        assert!((hit.normal - Vec3::new(0., 0., -1.)).length() < 1e-9);
+        assert!((hit.dpdu.normalized() - Vec3::new(1., 0., 0.)).length() < 1e-9);
+
+        let ray = Ray::new(Point::new(0.5, 0.15, -5.), Vec3::new(0., 0., 1.), 0.);
+        assert!(curve.hit(&ray, &Interval::positive()).is_none());
+
+        let ray = Ray::new(Point::new(1.05, 0., -5.), Vec3::new(0., 0., 1.), 0.);
+        assert!(curve.hit(&ray, &Interval::positive()).is_none());
+    }
+
+    #[test]
+    fn test_hit_cylinder() {
+        let curve = straight_curve(CurveKind::Cylinder);
+
+        let ray = Ray::new(Point::new(0., 0.1, -5.), Vec3::new(0., 0., 1.), 0.);
+        let hit = curve.hit(&ray, &Interval::positive()).unwrap();
+        assert!(hit.normal.y().abs() > 0.99);
+    }
+
+    #[test]
+    fn test_hit_bent() {
+        // Arc in the xy plane, hit at its top
+        let control_points = [
+            Point::new(-1., 0., 0.),
+            Point::new(-1., 1., 0.),
+            Point::new(1., 1., 0.),
+            Point::new(1., 0., 0.),
+        ];
+        let curve = Curve::new(control_points, 0.05, 0.05, CurveKind::Flat, Lambertian::new(Color::white()));
+
+        let ray = Ray::new(Point::new(0., 0.75, 5.), Vec3::new(0., 0., -1.), 0.);
+        let hit = curve.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 5.).abs() < 1e-9);
+        assert!((hit.uv.u - 0.5).abs() < 1e-2);
+
+        let ray = Ray::new(Point::new(0., 0.5, 5.), Vec3::new(0., 0., -1.), 0.);
+        assert!(curve.hit(&ray, &Interval::positive()).is_none());
+    }
+}
@@ -0,0 +1,283 @@
+use crate::vector::{Point, Vec3};
+use crate::hittable::{HitRecord, Hittable, AABB};
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::noise::Perlin;
+use crate::texture::Uv;
+
+use image::io::Reader as ImageReader;
+
+use std::rc::Rc;
+
+/// Terrain made of a regular grid of height samples, each cell being split in two triangles.
+/// It spans `size` from `corner`, along the x and z axes, heights in [0, 1] being scaled by `size.y()`.
+#[derive(Clone)]
+pub struct Heightfield {
+    corner: Point,
+    size: Vec3,
+    nx: usize,
+    nz: usize,
+    cell_dx: f64,
+    cell_dz: f64,
+
+    heights: Rc<Vec<f64>>,
+    normals: Rc<Vec<Vec3>>,
+    cell_bounds: Rc<Vec<Interval>>,
+
+    material: Rc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Heightfield {
+    /// `heights` holds `nx` samples per row, for `nz` rows (row major along z)
+    pub fn new(heights: Vec<f64>, nx: usize, nz: usize, corner: Point, size: Vec3, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        assert!(nx >= 2 && nz >= 2, "A heightfield needs at least 2x2 samples");
+        assert_eq!(heights.len(), nx * nz, "Expected {}x{} height samples", nx, nz);
+
+        let cell_dx = size.x() / (nx - 1) as f64;
+        let cell_dz = size.z() / (nz - 1) as f64;
+        let height_at = |i: usize, j: usize| heights[j * nx + i] * size.y();
+
+        // Smooth normals from the central differences of the heights
+        let mut normals = Vec::with_capacity(nx * nz);
+        for j in 0..nz {
+            for i in 0..nx {
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(nx - 1));
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(nz - 1));
+                let slope_x = (height_at(i1, j) - height_at(i0, j)) / ((i1 - i0) as f64 * cell_dx);
+                let slope_z = (height_at(i, j1) - height_at(i, j0)) / ((j1 - j0) as f64 * cell_dz);
+                normals.push(Vec3::new(-slope_x, 1., -slope_z).normalized());
+            }
+        }
+
+        // Height range of each cell, to skip most of them without testing their triangles
+        let mut cell_bounds = Vec::with_capacity((nx - 1) * (nz - 1));
+        for j in 0..nz - 1 {
+            for i in 0..nx - 1 {
+                let corners = [height_at(i, j), height_at(i + 1, j), height_at(i, j + 1), height_at(i + 1, j + 1)];
+                let min = corners.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = corners.iter().cloned().fold(-f64::INFINITY, f64::max);
+                cell_bounds.push(Interval::new(corner.y() + min, corner.y() + max));
+            }
+        }
+
+        let min_height = cell_bounds.iter().map(|b| b.min).fold(f64::INFINITY, f64::min);
+        let max_height = cell_bounds.iter().map(|b| b.max).fold(-f64::INFINITY, f64::max);
+        let bbox = AABB::new(
+            Interval::new(corner.x(), corner.x() + size.x()),
+            Interval::new(min_height, max_height),
+            Interval::new(corner.z(), corner.z() + size.z()),
+        ).pad();
+
+        Box::new(Self {
+            corner,
+            size,
+            nx,
+            nz,
+            cell_dx,
+            cell_dz,
+            heights: Rc::new(heights),
+            normals: Rc::new(normals),
+            cell_bounds: Rc::new(cell_bounds),
+            material,
+            bbox,
+        })
+    }
+
+    /// Heights from the luminance of a grayscale (or color) image, one sample per pixel
+    pub fn from_image(path: &str, corner: Point, size: Vec3, material: Rc<dyn Material>) -> Result<Box<dyn Hittable>, image::ImageError> {
+        let image = ImageReader::open(path)?.decode()?.to_luma8();
+        let (nx, nz) = (image.width() as usize, image.height() as usize);
+        let heights = image.pixels().map(|pixel| pixel[0] as f64 / 255.).collect();
+
+        Ok(Self::new(heights, nx, nz, corner, size, material))
+    }
+
+    /// Heights from the turbulence of a Perlin generator, sampled on a `resolution` x `resolution` grid.
+    /// `frequency` is the number of noise periods across the terrain.
+    pub fn from_noise(noise: &Perlin, resolution: usize, frequency: f64, corner: Point, size: Vec3, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        let step = frequency / (resolution - 1) as f64;
+        let mut heights = Vec::with_capacity(resolution * resolution);
+        for j in 0..resolution {
+            for i in 0..resolution {
+                heights.push(noise.turb_default(&Point::new(i as f64 * step, 0.5, j as f64 * step)));
+            }
+        }
+
+        // Normalize to [0, 1]
+        let min = heights.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = heights.iter().cloned().fold(-f64::INFINITY, f64::max);
+        let range = if max > min { max - min } else { 1. };
+        let heights = heights.into_iter().map(|h| (h - min) / range).collect();
+
+        Self::new(heights, resolution, resolution, corner, size, material)
+    }
+
+    fn vertex(&self, i: usize, j: usize) -> Point {
+        self.corner + Vec3::new(i as f64 * self.cell_dx, self.heights[j * self.nx + i] * self.size.y(), j as f64 * self.cell_dz)
+    }
+
+    fn hit_triangle(&self, ray: &Ray, ray_t: &Interval, vertices: [(usize, usize); 3]) -> Option<HitRecord> {
+        // Möller–Trumbore
+        let [a, b, c] = vertices.map(|(i, j)| self.vertex(i, j));
+        let edge1 = b - a;
+        let edge2 = c - a;
+
+        let p = ray.direction().cross(&edge2);
+        let det = edge1.dot(&p);
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let inv_det = 1. / det;
+
+        let s = *ray.origin() - a;
+        let beta = s.dot(&p) * inv_det;
+        if !(0. ..=1.).contains(&beta) {
+            return None;
+        }
+
+        let q = s.cross(&edge1);
+        let gamma = ray.direction().dot(&q) * inv_det;
+        if gamma < 0. || beta + gamma > 1. {
+            return None;
+        }
+
+        let t = edge2.dot(&q) * inv_det;
+        if !ray_t.surrounds(t) {
+            return None;
+        }
+
+        let alpha = 1. - beta - gamma;
+        let [na, nb, nc] = vertices.map(|(i, j)| self.normals[j * self.nx + i]);
+        let normal = alpha * na + beta * nb + gamma * nc;
+
+        let hit_point = ray.at(t);
+        let uv = Uv {
+            u: (hit_point.x() - self.corner.x()) / self.size.x(),
+            v: (hit_point.z() - self.corner.z()) / self.size.z(),
+        };
+
+        // Along the slope of the triangle, whose plane gives the height change for a change of x or z
+        let face_normal = edge1.cross(&edge2);
+        let dpdu = Vec3::new(self.size.x(), -self.size.x() * face_normal.x() / face_normal.y(), 0.);
+        let dpdv = Vec3::new(0., -self.size.z() * face_normal.z() / face_normal.y(), self.size.z());
+
+        Some(HitRecord::new(hit_point, normal, t, uv, ray, self.material.clone()).with_tangent_frame(dpdu, dpdv))
+    }
+
+    fn hit_cell(&self, ray: &Ray, ray_t: &Interval, i: usize, j: usize) -> Option<HitRecord> {
+        let first = self.hit_triangle(ray, ray_t, [(i, j), (i + 1, j), (i + 1, j + 1)]);
+        let max_t = first.as_ref().map_or(ray_t.max, |hr| hr.t);
+        self.hit_triangle(ray, &Interval::new(ray_t.min, max_t), [(i, j), (i + 1, j + 1), (i, j + 1)]).or(first)
+    }
+}
+
+impl Hittable for Heightfield {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let box_t = self.bbox.hit_interval(ray, ray_t)?;
+
+        // 2D DDA over the grid cells crossed by the ray, in the xz plane
+        let (cells_x, cells_z) = (self.nx - 1, self.nz - 1);
+        let entry = ray.at(box_t.min);
+        let mut i = (((entry.x() - self.corner.x()) / self.cell_dx).floor().max(0.) as usize).min(cells_x - 1);
+        let mut j = (((entry.z() - self.corner.z()) / self.cell_dz).floor().max(0.) as usize).min(cells_z - 1);
+
+        let direction = ray.direction();
+        let (step_i, t_delta_x, mut t_next_x) = if direction.x() > 0. {
+            (1, self.cell_dx / direction.x(), (self.corner.x() + (i + 1) as f64 * self.cell_dx - ray.origin().x()) / direction.x())
+        } else if direction.x() < 0. {
+            (-1, -self.cell_dx / direction.x(), (self.corner.x() + i as f64 * self.cell_dx - ray.origin().x()) / direction.x())
+        } else {
+            (0, f64::INFINITY, f64::INFINITY)
+        };
+        let (step_j, t_delta_z, mut t_next_z) = if direction.z() > 0. {
+            (1, self.cell_dz / direction.z(), (self.corner.z() + (j + 1) as f64 * self.cell_dz - ray.origin().z()) / direction.z())
+        } else if direction.z() < 0. {
+            (-1, -self.cell_dz / direction.z(), (self.corner.z() + j as f64 * self.cell_dz - ray.origin().z()) / direction.z())
+        } else {
+            (0, f64::INFINITY, f64::INFINITY)
+        };
+
+        let mut t_cell_enter = box_t.min;
+        loop {
+            let t_cell_exit = t_next_x.min(t_next_z).min(box_t.max);
+
+            // Skip the cell if the ray stays above or below it
+            let y_enter = ray.at(t_cell_enter).y();
+            let y_exit = ray.at(t_cell_exit).y();
+            let bounds = &self.cell_bounds[j * cells_x + i];
+            if y_enter.min(y_exit) <= bounds.max && y_enter.max(y_exit) >= bounds.min {
+                if let Some(hit_record) = self.hit_cell(ray, ray_t, i, j) {
+                    return Some(hit_record);
+                }
+            }
+
+            if t_cell_exit >= box_t.max {
+                return None;
+            }
+
+            // Move to the next cell
+            if t_next_x < t_next_z {
+                if (step_i < 0 && i == 0) || (step_i > 0 && i + 1 >= cells_x) {
+                    return None;
+                }
+                i = (i as isize + step_i) as usize;
+                t_next_x += t_delta_x;
+            } else {
+                if (step_j < 0 && j == 0) || (step_j > 0 && j + 1 >= cells_z) {
+                    return None;
+                }
+                j = (j as isize + step_j) as usize;
+                t_next_z += t_delta_z;
+            }
+            t_cell_enter = t_cell_exit;
+        }
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.bbox
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::Lambertian;
+    use crate::color::Color;
+
+    #[test]
+    fn test_hit_flat() {
+        let heightfield = Heightfield::new(vec![0.5; 16], 4, 4, Point::new(0., 0., 0.), Vec3::new(3., 2., 3.), Lambertian::new(Color::white()));
+
+        let ray = Ray::new(Point::new(1.2, 5., 2.7), Vec3::new(0., -1., 0.), 0.);
+        let hit = heightfield.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 4.).abs() < 1e-9);
+        assert!((hit.normal - Vec3::new(0., 1., 0.)).length() < 1e-9);
+        assert!((hit.uv.u - 0.4).abs() < 1e-9);
+        assert!((hit.uv.v - 0.9).abs() < 1e-9);
+
+        let ray = Ray::new(Point::new(4., 5., 1.), Vec3::new(0., -1., 0.), 0.);
+        assert!(heightfield.hit(&ray, &Interval::positive()).is_none());
+    }
+
+    #[test]
+    fn test_hit_slope_grazing() {
+        // Heights rising along x, hit by a ray travelling horizontally across many cells
+        let nx = 11;
+        let heights = (0..nx * 2).map(|k| (k % nx) as f64 / (nx - 1) as f64).collect();
+        let heightfield = Heightfield::new(heights, nx, 2, Point::new(0., 0., 0.), Vec3::new(10., 10., 1.), Lambertian::new(Color::white()));
+
+        let ray = Ray::new(Point::new(-1., 7.5, 0.5), Vec3::new(1., 0., 0.), 0.);
+        let hit = heightfield.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.point.x() - 7.5).abs() < 1e-9);
+
+        // The tangents follow the slope of 1 along x
+        assert!((hit.dpdu - Vec3::new(10., 10., 0.)).length() < 1e-9);
+        assert!((hit.dpdv - Vec3::new(0., 0., 1.)).length() < 1e-9);
+    }
+}
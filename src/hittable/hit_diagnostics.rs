@@ -0,0 +1,51 @@
+use crate::vector::Point;
+
+/// What a ray went through to find its closest hit, filled by `Hittable::hit_diagnostics` for the debug views
+#[derive(Clone, Debug, Default)]
+pub struct HitDiagnostics {
+    pub node_visits: usize,
+    pub primitive_tests: usize,
+
+    /// Identifies the primitive hit, and the instances it's in. Only stable during a run.
+    pub object_id: u64,
+
+    /// Closest point of the edges of the triangle or quad hit, if it has straight edges
+    pub edge_point: Option<Point>,
+}
+
+impl HitDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Closest point to `point` on the closed outline going through the corners
+pub(crate) fn closest_on_outline(point: &Point, corners: &[Point]) -> Point {
+    let mut closest = corners[0];
+    let mut closest_distance = f64::INFINITY;
+    for (i, &a) in corners.iter().enumerate() {
+        let edge = corners[(i + 1) % corners.len()] - a;
+        let along = if edge.near_zero() { 0. } else { ((*point - a).dot(&edge) / edge.length_squared()).clamp(0., 1.) };
+        let candidate = a + along * edge;
+
+        let distance = (*point - candidate).length_squared();
+        if distance < closest_distance {
+            closest = candidate;
+            closest_distance = distance;
+        }
+    }
+    closest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_on_outline() {
+        let square = [Point::new(0., 0., 0.), Point::new(1., 0., 0.), Point::new(1., 1., 0.), Point::new(0., 1., 0.)];
+        assert!((closest_on_outline(&Point::new(0.5, 0.1, 0.), &square) - Point::new(0.5, 0., 0.)).length() < 1e-12);
+        assert!((closest_on_outline(&Point::new(0.9, 0.6, 0.), &square) - Point::new(1., 0.6, 0.)).length() < 1e-12);
+        assert!((closest_on_outline(&Point::new(2., 2., 0.), &square) - Point::new(1., 1., 0.)).length() < 1e-12);
+    }
+}
@@ -0,0 +1,18 @@
+use crate::hittable::HitRecord;
+
+/// Part of a ray spent inside a closed object, between the two boundary hits
+#[derive(Clone)]
+pub struct HitInterval {
+    pub enter: HitRecord,
+    pub exit: HitRecord,
+}
+
+impl HitInterval {
+    pub fn new(enter: HitRecord, exit: HitRecord) -> Self {
+        Self { enter, exit }
+    }
+
+    pub fn length(&self) -> f64 {
+        self.exit.t - self.enter.t
+    }
+}
@@ -5,6 +5,7 @@ use crate::texture::Uv;
 
 use std::rc::Rc;
 
+#[derive(Clone)]
 pub struct HitRecord {
     pub point: Point,
     pub normal: Vec3,
@@ -12,6 +13,11 @@ pub struct HitRecord {
     pub uv: Uv,
     pub front_face: bool,
     pub material: Rc<dyn Material>,
+
+    /// Partial derivatives of the hit point along the u and v coordinates, spanning the tangent plane.
+    /// Zero when the object doesn't provide them.
+    pub dpdu: Vec3,
+    pub dpdv: Vec3,
 }
 
 impl HitRecord {
@@ -24,6 +30,26 @@ impl HitRecord {
             t,
             uv,
             material,
+            dpdu: Vec3::zero(),
+            dpdv: Vec3::zero(),
         }
     }
+
+    pub fn with_tangent_frame(mut self, dpdu: Vec3, dpdv: Vec3) -> Self {
+        self.dpdu = dpdu;
+        self.dpdv = dpdv;
+        self
+    }
+
+    /// Orthonormal (tangent, bitangent) pair around the normal, following dpdu and dpdv when available
+    pub fn tangent_frame(&self) -> (Vec3, Vec3) {
+        let tangent = self.dpdu - self.normal * self.normal.dot(&self.dpdu);
+        if tangent.near_zero() {
+            return self.normal.orthonormal_basis();
+        }
+
+        let tangent = tangent.normalized();
+        let bitangent = self.normal.cross(&tangent);
+        if bitangent.dot(&self.dpdv) < 0. { (tangent, -bitangent) } else { (tangent, bitangent) }
+    }
 }
\ No newline at end of file
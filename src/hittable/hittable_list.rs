@@ -1,10 +1,12 @@
-use crate::hittable::{Hittable, HitRecord, AABB, BvhTree};
+use crate::hittable::{Hittable, HitRecord, HitDiagnostics, AABB, BvhTree};
 use crate::ray::Ray;
+use crate::ray_packet::RayPacket;
 use crate::interval::Interval;
 
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::ops::AddAssign;
+use std::io::Error;
 
 #[derive(Clone)]
 pub struct HittableList {
@@ -44,6 +46,23 @@ impl HittableList {
             bbox,
         }
     }
+
+    /// Same as `to_bvh`, the tree being loaded from the cache file at `path` when it was built
+    /// for these objects by a previous run, else built and saved there
+    pub fn to_bvh_cached(&self, path: &str) -> Result<Self, Error> {
+        let tree = BvhTree::from_list_cached(self, path)?;
+        let bbox = tree.bounding_box();
+
+        Ok(Self {
+            objects: Rc::new(RefCell::new(vec![Box::new(tree)])),
+            bbox,
+        })
+    }
+
+    /// Bottom-level BVH of the objects, to be shared by several `Instance`s
+    pub fn to_blas(&self) -> Rc<dyn Hittable> {
+        Rc::new(BvhTree::from_list(self))
+    }
 }
 
 impl Hittable for HittableList {
@@ -61,6 +80,37 @@ impl Hittable for HittableList {
         hit_record
     }
 
+    fn hit_diagnostics(&self, ray: &Ray, ray_t: &Interval, diagnostics: &mut HitDiagnostics) -> Option<HitRecord> {
+        let mut hit_record = None;
+        let mut min_dist = ray_t.max;
+
+        for object in self.objects.borrow().iter() {
+            if let Some(hit_rec) = object.hit_diagnostics(ray, &Interval::new(ray_t.min, min_dist), diagnostics) {
+                min_dist = hit_rec.t;
+                hit_record = Some(hit_rec);
+            }
+        }
+
+        hit_record
+    }
+
+    fn hit_packet(&self, packet: &RayPacket, active: u16, ray_t: &Interval, hits: &mut [Option<HitRecord>]) {
+        for object in self.objects.borrow().iter() {
+            object.hit_packet(packet, active, ray_t, hits);
+        }
+    }
+
+    fn occluded_packet(&self, packet: &RayPacket, active: u16, ray_t: &Interval) -> u16 {
+        let mut occluded = 0;
+        for object in self.objects.borrow().iter() {
+            occluded |= object.occluded_packet(packet, active & !occluded, ray_t);
+            if occluded == active {
+                break;
+            }
+        }
+        occluded
+    }
+
     fn bounding_box(&self) -> AABB {
         self.bbox
     }
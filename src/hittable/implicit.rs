@@ -0,0 +1,168 @@
+use crate::vector::Point;
+use crate::hittable::{HitRecord, Hittable, AABB};
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::implicit::ImplicitFunction;
+use crate::texture::Uv;
+
+use std::rc::Rc;
+
+const DEFAULT_STEPS: usize = 256;
+const BISECTION_STEPS: usize = 64;
+
+/// Surface f(p) = 0 inside `bbox`. Polynomial functions are solved exactly along the ray,
+/// the others are sampled in `steps` steps across the box and refined by bisection.
+#[derive(Clone)]
+pub struct Implicit {
+    function: Rc<dyn ImplicitFunction>,
+    steps: usize,
+    material: Rc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Implicit {
+    pub fn new(function: Rc<dyn ImplicitFunction>, bbox: AABB, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        Self::with_steps(function, bbox, DEFAULT_STEPS, material)
+    }
+
+    pub fn with_steps(function: Rc<dyn ImplicitFunction>, bbox: AABB, steps: usize, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        Box::new(Self {
+            function,
+            steps: steps.max(1),
+            material,
+            bbox: bbox.pad(),
+        })
+    }
+
+    fn find_root(&self, ray: &Ray, search_t: &Interval) -> Option<f64> {
+        if let Some(polynomial) = self.function.along_ray(ray) {
+            return polynomial.roots(search_t).into_iter().next();
+        }
+
+        // Look for the first sign change, then refine it
+        let step = search_t.size() / self.steps as f64;
+        let mut t0 = search_t.min;
+        let mut f0 = self.function.value(&ray.at(t0));
+
+        for k in 1..=self.steps {
+            let t1 = search_t.min + k as f64 * step;
+            let f1 = self.function.value(&ray.at(t1));
+
+            if f0 == 0. {
+                return Some(t0);
+            }
+            if f0.signum() != f1.signum() {
+                return Some(self.bisect(ray, Interval::new(t0, t1), f0));
+            }
+
+            t0 = t1;
+            f0 = f1;
+        }
+
+        None
+    }
+
+    fn bisect(&self, ray: &Ray, mut bracket: Interval, mut f_min: f64) -> f64 {
+        for _ in 0..BISECTION_STEPS {
+            let mid = 0.5 * (bracket.min + bracket.max);
+            let f_mid = self.function.value(&ray.at(mid));
+            if f_mid.signum() == f_min.signum() {
+                bracket.min = mid;
+                f_min = f_mid;
+            } else {
+                bracket.max = mid;
+            }
+        }
+        0.5 * (bracket.min + bracket.max)
+    }
+
+    fn compute_uv(&self, p: &Point) -> Uv {
+        // Spherical mapping around the center of the bounding box
+        let center = Point::new(
+            0.5 * (self.bbox.x().min + self.bbox.x().max),
+            0.5 * (self.bbox.y().min + self.bbox.y().max),
+            0.5 * (self.bbox.z().min + self.bbox.z().max),
+        );
+        let direction = (*p - center).normalized();
+        let theta = (-direction.y()).acos();
+        let phi = (-direction.z()).atan2(direction.x()) + std::f64::consts::PI;
+
+        Uv {
+            u: phi / (2. * std::f64::consts::PI),
+            v: theta / std::f64::consts::PI,
+        }
+    }
+}
+
+impl Hittable for Implicit {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let box_t = self.bbox.hit_interval(ray, ray_t)?;
+
+        // Keep away from the starting point, to avoid finding again the surface a scattered ray leaves
+        let search_t = Interval::new(box_t.min.max(ray_t.min + 1e-4 / ray.direction().length()), box_t.max);
+        if search_t.size() <= 0. {
+            return None;
+        }
+
+        let t = self.find_root(ray, &search_t)?;
+        if !ray_t.surrounds(t) {
+            return None;
+        }
+
+        let hit_point = ray.at(t);
+        let normal = self.function.gradient(&hit_point);
+        if normal.near_zero() {
+            return None;
+        }
+        let uv = self.compute_uv(&hit_point);
+
+        Some(HitRecord::new(hit_point, normal, t, uv, ray, self.material.clone()))
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.bbox
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::Vec3;
+    use crate::implicit::{AlgebraicSurface, Metaballs};
+    use crate::material::Lambertian;
+    use crate::color::Color;
+
+    #[test]
+    fn test_hit_torus() {
+        let bbox = AABB::from_points(Point::new(-3., -1., -3.), Point::new(3., 1., 3.));
+        let torus = Implicit::new(AlgebraicSurface::torus(2., 0.5), bbox, Lambertian::new(Color::white()));
+
+        let ray = Ray::new(Point::new(-5., 0., 0.), Vec3::new(1., 0., 0.), 0.);
+        let hit = torus.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 2.5).abs() < 1e-6);
+        assert!((hit.normal - Vec3::new(-1., 0., 0.)).length() < 1e-6);
+        assert!(hit.front_face);
+
+        // Through the hole
+        let ray = Ray::new(Point::new(0., 5., 0.), Vec3::new(0., -1., 0.), 0.);
+        assert!(torus.hit(&ray, &Interval::positive()).is_none());
+    }
+
+    #[test]
+    fn test_hit_metaballs() {
+        let bbox = AABB::from_points(Point::new(-2., -2., -2.), Point::new(2., 2., 2.));
+        let metaballs = Metaballs::new(vec![(Point::new(0., 0., 0.), 1.)], 0.125);
+        let blob = Implicit::new(metaballs, bbox, Lambertian::new(Color::white()));
+
+        // (1 - d²)³ = 1/8 for d² = 1/2
+        let ray = Ray::new(Point::new(0., 0., -5.), Vec3::new(0., 0., 1.), 0.);
+        let hit = blob.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - (5. - 0.5f64.sqrt())).abs() < 1e-6);
+        assert!(hit.front_face);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::hittable::{HitRecord, HitInterval, HitDiagnostics, Hittable, AABB};
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::transform::Transform;
+
+use std::rc::Rc;
+
+/// Placement of a shared object (typically a bottom-level BVH from `HittableList::to_blas`)
+/// with its own transform. Many instances of the same object only store it once.
+#[derive(Clone)]
+pub struct Instance {
+    object: Rc<dyn Hittable>,
+    transform: Transform,
+    bbox: AABB,
+}
+
+impl Instance {
+    pub fn new(object: Rc<dyn Hittable>, transform: Transform) -> Box<dyn Hittable> {
+        let bbox = transform.bounding_box(&object.bounding_box());
+        Box::new(Self {
+            object,
+            transform,
+            bbox,
+        })
+    }
+
+    fn to_local(&self, ray: &Ray) -> Ray {
+        // The direction isn't normalized, so t is the same in both spaces
+        let inverse = self.transform.inverse();
+        Ray::new(inverse.point(ray.origin()), inverse.vector(ray.direction()), ray.time())
+    }
+
+    fn to_world(&self, mut hit_record: HitRecord) -> HitRecord {
+        // front_face doesn't change, as the dot product of a direction and a normal is preserved
+        hit_record.point = self.transform.point(&hit_record.point);
+        hit_record.normal = self.transform.normal(&hit_record.normal).normalized();
+        hit_record.dpdu = self.transform.vector(&hit_record.dpdu);
+        hit_record.dpdv = self.transform.vector(&hit_record.dpdv);
+        hit_record
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        if !self.bbox.hit(ray, ray_t) {
+            return None;
+        }
+
+        self.object.hit(&self.to_local(ray), ray_t).map(|hit_record| self.to_world(hit_record))
+    }
+
+    fn hit_diagnostics(&self, ray: &Ray, ray_t: &Interval, diagnostics: &mut HitDiagnostics) -> Option<HitRecord> {
+        if !self.bbox.hit(ray, ray_t) {
+            return None;
+        }
+
+        let hit_record = self.object.hit_diagnostics(&self.to_local(ray), ray_t, diagnostics)?;
+
+        // Tell apart the instances sharing the primitive
+        let address = (self as *const Self) as usize as u64;
+        diagnostics.object_id = (diagnostics.object_id ^ address.rotate_left(32)).wrapping_mul(0x100000001b3);
+        diagnostics.edge_point = diagnostics.edge_point.map(|point| self.transform.point(&point));
+        Some(self.to_world(hit_record))
+    }
+
+    fn hit_intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        self.object.hit_intervals(&self.to_local(ray))
+            .into_iter()
+            .map(|interval| HitInterval::new(self.to_world(interval.enter), self.to_world(interval.exit)))
+            .collect()
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.bbox
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::{HittableList, Sphere};
+    use crate::material::Lambertian;
+    use crate::color::Color;
+    use crate::vector::{Point, Vec3};
+
+    #[test]
+    fn test_scaled_instances() {
+        let mut unit = HittableList::new();
+        unit += Sphere::new(Point::new(0., 0., 0.), 1., Lambertian::new(Color::white()));
+        let blas = unit.to_blas();
+
+        // Two flattened spheres sharing the same geometry
+        let mut world = HittableList::new();
+        for x in [-5., 5.] {
+            let transform = Transform::scaling(Vec3::new(1., 2., 1.)).then_translate(Vec3::new(x, 0., 0.));
+            world += Instance::new(blas.clone(), transform);
+        }
+        let world = world.to_bvh();
+
+        let ray = Ray::new(Point::new(5., 10., 0.), Vec3::new(0., -1., 0.), 0.);
+        let hit = world.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 8.).abs() < 1e-9);
+        assert!((hit.point - Point::new(5., 2., 0.)).length() < 1e-9);
+        assert!((hit.normal - Vec3::new(0., 1., 0.)).length() < 1e-9);
+        assert!(hit.front_face);
+
+        // The side normal gets squashed by the scaling
+        let ray = Ray::new(Point::new(-10., 2f64.sqrt(), 0.), Vec3::new(1., 0., 0.), 0.);
+        let hit = world.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.point.x() + 5. + 0.5f64.sqrt()).abs() < 1e-9);
+        assert!((hit.normal - Vec3::new(-2., 1., 0.).normalized()).length() < 1e-9);
+    }
+}
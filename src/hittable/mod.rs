@@ -1,28 +1,113 @@
 use crate::interval::Interval;
 use crate::ray::Ray;
+use crate::ray_packet::RayPacket;
+use crate::vector::Point;
 
 mod aabb;
 mod cuboid;
 mod bvh;
 mod constant_medium;
+mod subsurface;
 mod sphere;
 mod quad;
+mod planar_shape;
+mod sdf;
+mod heightfield;
+mod implicit;
+mod curve;
+mod triangle_mesh;
 mod hittable_list;
 mod hit_record;
+mod hit_interval;
+mod hit_diagnostics;
+mod csg;
+mod instance;
 
 pub use aabb::AABB;
 pub use cuboid::*;
 pub use bvh::BvhTree;
 pub use constant_medium::ConstantMedium;
+pub use subsurface::Subsurface;
 pub use sphere::Sphere;
 pub use quad::Quad;
+pub use planar_shape::{PlanarShape, Parallelogram, Triangle, Ellipse, Annulus};
+pub use sdf::Sdf;
+pub use heightfield::Heightfield;
+pub use implicit::Implicit;
+pub use curve::{Curve, CurveKind};
+pub use triangle_mesh::triangle_mesh;
 pub use hittable_list::HittableList;
 pub use hit_record::HitRecord;
+pub use hit_interval::HitInterval;
+pub use hit_diagnostics::HitDiagnostics;
+pub(crate) use hit_diagnostics::closest_on_outline;
+pub use csg::{Csg, CsgOperation};
+pub use instance::Instance;
 
 pub trait Hittable {
     fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord>;
     fn bounding_box(&self) -> AABB;
 
+    /// All the intervals the ray spends inside the object, sorted along the whole ray line (negative t included).
+    /// Only meaningful for closed objects. The default implementation walks the successive hits and pairs
+    /// them two by two, as the line starts outside of the object (so it doesn't rely on the normals orientation).
+    fn hit_intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        const MAX_HITS: usize = 64;
+
+        let mut intervals = Vec::new();
+        let mut enter: Option<HitRecord> = None;
+        let mut t_min = -f64::INFINITY;
+
+        for _ in 0..MAX_HITS {
+            let hit_record = match self.hit(ray, &Interval::new(t_min, f64::INFINITY)) {
+                Some(hr) => hr,
+                None => break,
+            };
+            t_min = hit_record.t + 0.0001;
+
+            match enter.take() {
+                None => enter = Some(hit_record),
+                Some(enter_record) => intervals.push(HitInterval::new(enter_record, hit_record)),
+            }
+        }
+
+        intervals
+    }
+
+    /// Intersects the rays of the `active` mask of the packet, keeping in `hits` the closest hit of every ray
+    /// (the hits already there bound the search). The default implementation traces the rays one by one.
+    fn hit_packet(&self, packet: &RayPacket, active: u16, ray_t: &Interval, hits: &mut [Option<HitRecord>]) {
+        for (i, ray) in packet.active(active) {
+            let closest = hits[i].as_ref().map_or(ray_t.max, |hit| hit.t);
+            if let Some(hit_record) = self.hit(ray, &Interval::new(ray_t.min, closest)) {
+                hits[i] = Some(hit_record);
+            }
+        }
+    }
+
+    /// Mask of the `active` rays of the packet hitting anything during `ray_t`, like shadow rays.
+    /// The camera doesn't trace shadow rays (it samples lights by bouncing), so only `bvh_benchmark` uses it for now.
+    fn occluded_packet(&self, packet: &RayPacket, active: u16, ray_t: &Interval) -> u16 {
+        packet.active(active)
+            .filter(|(_, ray)| self.hit(ray, ray_t).is_some())
+            .fold(0, |occluded, (i, _)| occluded | 1 << i)
+    }
+
+    /// Same as `hit`, also telling what it took to find the hit for the debug views. The default
+    /// implementation counts a single primitive test, the object being identified by its address.
+    fn hit_diagnostics(&self, ray: &Ray, ray_t: &Interval, diagnostics: &mut HitDiagnostics) -> Option<HitRecord> {
+        diagnostics.primitive_tests += 1;
+        let hit_record = self.hit(ray, ray_t)?;
+        diagnostics.object_id = (self as *const Self).cast::<()>() as usize as u64;
+        diagnostics.edge_point = self.closest_edge(&hit_record.point);
+        Some(hit_record)
+    }
+
+    /// Closest point of the edges of the primitive to a point on its surface, for the primitives with straight edges
+    fn closest_edge(&self, _point: &Point) -> Option<Point> {
+        None
+    }
+
     fn box_clone(&self) -> Box<dyn Hittable>;
 }
 
@@ -0,0 +1,136 @@
+use crate::vector::{Point, Vec3};
+use crate::hittable::AABB;
+use crate::texture::Uv;
+
+/// Interior test of a planar primitive, expressed in the plane-local (alpha, beta) coordinates
+/// of the (Q, u, v) frame. Returns the uv coordinates of the hit point if it lies inside the shape.
+pub trait PlanarShape {
+    fn interior(&self, alpha: f64, beta: f64) -> Option<Uv>;
+
+    /// Derivatives of the point along the uv coordinates returned by `interior`
+    fn tangent_frame(&self, u: Vec3, v: Vec3) -> (Vec3, Vec3) {
+        (u, v)
+    }
+
+    fn bounding_box(&self, q: Point, u: Vec3, v: Vec3) -> AABB {
+        AABB::from_points(q, q + u + v).surrounding_box(&AABB::from_points(q + u, q + v))
+    }
+
+    /// Corners of the shape, when its outline is a polygon
+    fn outline(&self, _q: Point, _u: Vec3, _v: Vec3) -> Option<Vec<Point>> {
+        None
+    }
+}
+
+/// Parallelogram spanned by u and v from the corner Q.
+pub struct Parallelogram;
+
+impl PlanarShape for Parallelogram {
+    fn interior(&self, alpha: f64, beta: f64) -> Option<Uv> {
+        if !(0. ..=1.).contains(&alpha) || !(0. ..=1.).contains(&beta) {
+            return None;
+        }
+
+        Some(Uv { u: alpha, v: beta })
+    }
+
+    fn outline(&self, q: Point, u: Vec3, v: Vec3) -> Option<Vec<Point>> {
+        Some(vec![q, q + u, q + u + v, q + v])
+    }
+}
+
+/// Triangle with vertices Q, Q + u and Q + v.
+pub struct Triangle;
+
+impl PlanarShape for Triangle {
+    fn interior(&self, alpha: f64, beta: f64) -> Option<Uv> {
+        if alpha < 0. || beta < 0. || alpha + beta > 1. {
+            return None;
+        }
+
+        Some(Uv { u: alpha, v: beta })
+    }
+
+    fn outline(&self, q: Point, u: Vec3, v: Vec3) -> Option<Vec<Point>> {
+        Some(vec![q, q + u, q + v])
+    }
+}
+
+/// Ellipse centered on Q, with u and v as semi-axes.
+pub struct Ellipse;
+
+impl PlanarShape for Ellipse {
+    fn interior(&self, alpha: f64, beta: f64) -> Option<Uv> {
+        if alpha * alpha + beta * beta > 1. {
+            return None;
+        }
+
+        Some(Uv { u: alpha / 2. + 0.5, v: beta / 2. + 0.5 })
+    }
+
+    fn tangent_frame(&self, u: Vec3, v: Vec3) -> (Vec3, Vec3) {
+        (2. * u, 2. * v)
+    }
+
+    fn bounding_box(&self, q: Point, u: Vec3, v: Vec3) -> AABB {
+        AABB::from_points(q - u - v, q + u + v).surrounding_box(&AABB::from_points(q - u + v, q + u - v))
+    }
+}
+
+/// Ellipse centered on Q with an elliptic hole, `inner` being the ratio between the inner and outer radii.
+pub struct Annulus {
+    inner: f64,
+}
+
+impl Annulus {
+    pub fn new(inner: f64) -> Self {
+        Self { inner: inner.clamp(0., 1.) }
+    }
+}
+
+impl PlanarShape for Annulus {
+    fn interior(&self, alpha: f64, beta: f64) -> Option<Uv> {
+        let radius_squared = alpha * alpha + beta * beta;
+        if radius_squared > 1. || radius_squared < self.inner * self.inner {
+            return None;
+        }
+
+        Some(Uv { u: alpha / 2. + 0.5, v: beta / 2. + 0.5 })
+    }
+
+    fn tangent_frame(&self, u: Vec3, v: Vec3) -> (Vec3, Vec3) {
+        (2. * u, 2. * v)
+    }
+
+    fn bounding_box(&self, q: Point, u: Vec3, v: Vec3) -> AABB {
+        Ellipse.bounding_box(q, u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triangle_interior() {
+        assert!(Triangle.interior(0.25, 0.25).is_some());
+        assert!(Triangle.interior(0.75, 0.5).is_none());
+        assert!(Triangle.interior(-0.1, 0.5).is_none());
+    }
+
+    #[test]
+    fn test_ellipse_interior() {
+        let uv = Ellipse.interior(0., 0.).unwrap();
+        assert_eq!(uv.u, 0.5);
+        assert_eq!(uv.v, 0.5);
+        assert!(Ellipse.interior(0.8, 0.8).is_none());
+    }
+
+    #[test]
+    fn test_annulus_interior() {
+        let annulus = Annulus::new(0.5);
+        assert!(annulus.interior(0., 0.).is_none());
+        assert!(annulus.interior(0.75, 0.).is_some());
+        assert!(annulus.interior(1.1, 0.).is_none());
+    }
+}
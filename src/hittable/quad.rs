@@ -1,9 +1,8 @@
 use crate::vector::{Point, Vec3};
-use crate::hittable::{HitRecord, Hittable, AABB};
+use crate::hittable::{HitRecord, Hittable, AABB, closest_on_outline, PlanarShape, Parallelogram, Triangle, Ellipse, Annulus};
 use crate::interval::Interval;
 use crate::ray::Ray;
 use crate::material::Material;
-use crate::texture::Uv;
 
 use std::rc::Rc;
 
@@ -15,13 +14,35 @@ pub struct Quad {
     w: Vec3,
     normal: Vec3,
     d: f64,
+    shape: Rc<dyn PlanarShape>,
     material: Rc<dyn Material>,
     bbox: AABB,
 }
 
 impl Quad {
     pub fn new(q: Point, u: Vec3, v: Vec3, material: Rc<dyn Material>) -> Box<dyn Hittable> {
-        let bbox = AABB::from_points(q, q + u + v);
+        Self::with_shape(q, u, v, Rc::new(Parallelogram), material)
+    }
+
+    pub fn triangle(a: Point, b: Point, c: Point, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        Self::with_shape(a, b - a, c - a, Rc::new(Triangle), material)
+    }
+
+    pub fn ellipse(center: Point, u: Vec3, v: Vec3, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        Self::with_shape(center, u, v, Rc::new(Ellipse), material)
+    }
+
+    pub fn disk(center: Point, normal: Vec3, radius: f64, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        let (u, v) = normal.normalized().orthonormal_basis();
+        Self::ellipse(center, u * radius, v * radius, material)
+    }
+
+    pub fn annulus(center: Point, u: Vec3, v: Vec3, inner: f64, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        Self::with_shape(center, u, v, Rc::new(Annulus::new(inner)), material)
+    }
+
+    pub fn with_shape(q: Point, u: Vec3, v: Vec3, shape: Rc<dyn PlanarShape>, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        let bbox = shape.bounding_box(q, u, v);
 
         let normal = u.cross(&v);
         let w = normal / (normal.dot(&normal));
@@ -29,7 +50,7 @@ impl Quad {
         let normal = normal.normalized();
         let d = normal.dot(&q);
 
-        Box::new(Self { q, u, v, w, material, bbox, normal, d })
+        Box::new(Self { q, u, v, w, shape, material, bbox, normal, d })
     }
 }
 
@@ -39,33 +60,36 @@ impl Hittable for Quad {
     }
 
     fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
-        let denom = self.normal.dot(&ray.direction());
+        let denom = self.normal.dot(ray.direction());
         if denom == 0. {
             return None;
         }
 
-        let t = (self.d - self.normal.dot(&ray.origin())) / denom;
+        let t = (self.d - self.normal.dot(ray.origin())) / denom;
         if !ray_t.contains(t) {
             return None;
         }
         
-        // Determine if the intersection is in the plane
+        // Determine if the intersection is in the shape, using the plane coordinates
         let hit_point = ray.at(t);
         let p = hit_point - self.q;
         let alpha = self.w.dot(&p.cross(&self.v));
         let beta = self.w.dot(&self.u.cross(&p));
 
-        if alpha < 0. || beta < 0. || alpha > 1. || beta > 1. {
-            return None;
-        }
+        let uv = self.shape.interior(alpha, beta)?;
         
         // Build the hit record
-        let uv = Uv{ u: alpha, v: beta };  
+        let (dpdu, dpdv) = self.shape.tangent_frame(self.u, self.v);
 
-        Some(HitRecord::new(hit_point, self.normal, t, uv, ray, self.material.clone()))
+        Some(HitRecord::new(hit_point, self.normal, t, uv, ray, self.material.clone()).with_tangent_frame(dpdu, dpdv))
+    }
+
+    fn closest_edge(&self, point: &Point) -> Option<Point> {
+        let corners = self.shape.outline(self.q, self.u, self.v)?;
+        Some(closest_on_outline(point, &corners))
     }
 
     fn box_clone(&self) -> Box<dyn Hittable> {
         Box::new(self.clone())
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,152 @@
+use crate::vector::{Point, Vec3};
+use crate::hittable::{HitRecord, Hittable, AABB};
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::sdf::DistanceFunction;
+use crate::texture::Uv;
+
+use std::rc::Rc;
+
+const MAX_STEPS: usize = 512;
+const EPSILON: f64 = 1e-4;
+
+/// Surface defined by a signed distance function, rendered by sphere tracing inside `bbox`
+#[derive(Clone)]
+pub struct Sdf {
+    distance: Rc<dyn DistanceFunction>,
+    step_scale: f64,
+    material: Rc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Sdf {
+    pub fn new(distance: Rc<dyn DistanceFunction>, bbox: AABB, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        Self::with_step_scale(distance, bbox, 1., material)
+    }
+
+    /// `step_scale` < 1 shortens every step, for distance functions that overestimate the distance (twists, fractals...)
+    pub fn with_step_scale(distance: Rc<dyn DistanceFunction>, bbox: AABB, step_scale: f64, material: Rc<dyn Material>) -> Box<dyn Hittable> {
+        Box::new(Self {
+            distance,
+            step_scale: step_scale.clamp(0.01, 1.),
+            material,
+            bbox: bbox.pad(),
+        })
+    }
+
+    fn normal(&self, p: &Point) -> Vec3 {
+        // Gradient estimated with the tetrahedron technique (4 evaluations instead of 6)
+        let h = EPSILON;
+        let k1 = Vec3::new(1., -1., -1.);
+        let k2 = Vec3::new(-1., -1., 1.);
+        let k3 = Vec3::new(-1., 1., -1.);
+        let k4 = Vec3::new(1., 1., 1.);
+
+        let gradient = k1 * self.distance.distance(&(*p + k1 * h))
+            + k2 * self.distance.distance(&(*p + k2 * h))
+            + k3 * self.distance.distance(&(*p + k3 * h))
+            + k4 * self.distance.distance(&(*p + k4 * h));
+
+        if gradient.near_zero() {
+            return Vec3::new(0., 1., 0.);
+        }
+        gradient.normalized()
+    }
+
+    fn compute_uv(normal: &Vec3) -> Uv {
+        let theta = (-normal.y()).acos();
+        let phi = (-normal.z()).atan2(normal.x()) + std::f64::consts::PI;
+
+        Uv {
+            u: phi / (2. * std::f64::consts::PI),
+            v: theta / std::f64::consts::PI,
+        }
+    }
+}
+
+impl Hittable for Sdf {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let box_t = self.bbox.hit_interval(ray, ray_t)?;
+
+        let ray_length = ray.direction().length();
+        let min_step = EPSILON / ray_length;
+        let mut t = box_t.min;
+
+        // Rays starting inside the box can start right on the surface (scattered rays): move away first,
+        // then march from the outside or from the inside depending on where we are
+        let mut steps = 0;
+        let sign = if box_t.min <= ray_t.min {
+            while self.distance.distance(&ray.at(t)).abs() < EPSILON && steps < MAX_STEPS {
+                t += 2. * min_step;
+                steps += 1;
+            }
+            self.distance.distance(&ray.at(t)).signum()
+        } else {
+            1.
+        };
+
+        while t <= box_t.max && steps < MAX_STEPS {
+            let hit_point = ray.at(t);
+            let d = sign * self.distance.distance(&hit_point);
+
+            if d < EPSILON {
+                if !ray_t.surrounds(t) {
+                    return None;
+                }
+
+                let normal = self.normal(&hit_point);
+                let uv = Self::compute_uv(&normal);
+                return Some(HitRecord::new(hit_point, normal, t, uv, ray, self.material.clone()));
+            }
+
+            t += (d * self.step_scale / ray_length).max(min_step);
+            steps += 1;
+        }
+
+        None
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.bbox
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::Lambertian;
+    use crate::color::Color;
+    use crate::sdf::{SdfSphere, SdfBox, Translate};
+
+    #[test]
+    fn test_hit_sphere() {
+        let bbox = AABB::from_points(Point::new(-1., -1., -1.), Point::new(1., 1., 1.));
+        let sdf = Sdf::new(SdfSphere::new(1.), bbox, Lambertian::new(Color::white()));
+
+        let ray = Ray::new(Point::new(0., 0., -5.), Vec3::new(0., 0., 2.), 0.);
+        let hit = sdf.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 2.).abs() < 1e-3);
+        assert!((hit.normal - Vec3::new(0., 0., -1.)).length() < 1e-3);
+        assert!(hit.front_face);
+
+        let ray = Ray::new(Point::new(0., 2., -5.), Vec3::new(0., 0., 1.), 0.);
+        assert!(sdf.hit(&ray, &Interval::positive()).is_none());
+    }
+
+    #[test]
+    fn test_hit_from_inside() {
+        let offset = Vec3::new(3., 0., 0.);
+        let bbox = AABB::from_points(Point::new(2., -1., -1.), Point::new(4., 1., 1.));
+        let sdf = Sdf::new(Translate::new(SdfBox::new(Vec3::new(2., 2., 2.)), offset), bbox, Lambertian::new(Color::white()));
+
+        let ray = Ray::new(Point::new(3., 0., 0.), Vec3::new(1., 0., 0.), 0.);
+        let hit = sdf.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 1.).abs() < 1e-3);
+        assert!(!hit.front_face);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::vector::{Point, Vec3};
-use crate::hittable::{HitRecord, Hittable, AABB};
+use crate::hittable::{HitRecord, HitInterval, Hittable, AABB};
 use crate::interval::Interval;
 use crate::ray::Ray;
 use crate::material::Material;
@@ -55,6 +55,21 @@ impl Sphere {
         self.radius
     }
 
+    fn compute_tangent_frame(&self, p: &Vec3) -> (Vec3, Vec3) {
+        // p must be a unit vector, derivatives of the point along the uv coordinates of compute_uv
+
+        let radius = self.radius.abs();
+        let sin_theta = (p.x() * p.x() + p.z() * p.z()).sqrt();
+        if sin_theta < 1e-9 {
+            let (u, v) = p.orthonormal_basis();
+            return (2. * std::f64::consts::PI * radius * u, std::f64::consts::PI * radius * v);
+        }
+
+        let dpdu = 2. * std::f64::consts::PI * radius * Vec3::new(p.z(), 0., -p.x());
+        let dpdv = std::f64::consts::PI * radius * Vec3::new(-p.x() * p.y() / sin_theta, sin_theta, -p.y() * p.z() / sin_theta);
+        (dpdu, dpdv)
+    }
+
     fn compute_uv(p: &Vec3) -> Uv {
         // p must be a unit vector
 
@@ -95,13 +110,42 @@ impl Hittable for Sphere {
             let hit_point = ray.at(t);
             let normal = ((hit_point - center) / self.radius).normalized();
             let uv = Self::compute_uv(&normal);
+            let (dpdu, dpdv) = self.compute_tangent_frame(&normal);
 
-            return Some(HitRecord::new(hit_point, normal, t, uv, ray, self.material.clone()));
+            return Some(HitRecord::new(hit_point, normal, t, uv, ray, self.material.clone()).with_tangent_frame(dpdu, dpdv));
         }
 
         None
     }
 
+    fn hit_intervals(&self, ray: &Ray) -> Vec<HitInterval> {
+        let center = if self.is_moving {
+            self.center(ray.time())
+        } else {
+            self.initial_center
+        };
+
+        let oc = *ray.origin() - center;
+        let a = ray.direction().dot(ray.direction());
+        let b_half = ray.direction().dot(&oc);
+        let c = oc.dot(&oc) - self.radius * self.radius;
+
+        let delta_quarter = b_half * b_half -  a * c;
+        if delta_quarter <= 0. {
+            return Vec::new();
+        }
+
+        let [enter, exit] = [(-b_half - delta_quarter.sqrt()) / a, (-b_half + delta_quarter.sqrt()) / a].map(|t| {
+            let hit_point = ray.at(t);
+            let normal = ((hit_point - center) / self.radius).normalized();
+            let uv = Self::compute_uv(&normal);
+            let (dpdu, dpdv) = self.compute_tangent_frame(&normal);
+            HitRecord::new(hit_point, normal, t, uv, ray, self.material.clone()).with_tangent_frame(dpdu, dpdv)
+        });
+
+        vec![HitInterval::new(enter, exit)]
+    }
+
     fn bounding_box(&self) -> AABB {
         self.bbox
     }
@@ -0,0 +1,188 @@
+use crate::hittable::{HitRecord, Hittable, AABB};
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::color::Color;
+use crate::material::{Material, HenyeyGreenstein, RoughDielectric};
+use crate::texture::Uv;
+use crate::vector::Vec3;
+
+use std::rc::Rc;
+use rand::Rng;
+
+/// Translucent object, like skin, wax, milk or marble: light refracts through a dielectric boundary, then
+/// random-walks inside, scattering with a Henyey-Greenstein phase function until it's absorbed or gets out.
+/// Like `ConstantMedium`, the boundary must be closed. Long walks need a high max depth on the camera.
+///
+/// Every step samples its distance with the extinction of one of the channels, picked at random, and weights
+/// the channels by their transmittance over the mean density of the three (spectral MIS), so that red light
+/// can travel further than blue.
+#[derive(Clone)]
+pub struct Subsurface {
+    boundary: Box<dyn Hittable>,
+    interface: Rc<dyn Material>,
+    sigma_s: Color,
+    sigma_t: Color,
+    g: f64,
+}
+
+impl Subsurface {
+    /// Scattering and absorption coefficients per unit of length, for each channel.
+    /// `g` is the mean cosine of the phase function.
+    pub fn new(boundary: Box<dyn Hittable>, refraction_index: f64, roughness: f64, sigma_s: Color, sigma_a: Color, g: f64) -> Box<dyn Hittable> {
+        Box::new(Self {
+            boundary,
+            interface: RoughDielectric::new(refraction_index, roughness),
+            sigma_t: sigma_s.clone() + sigma_a,
+            sigma_s,
+            g,
+        })
+    }
+
+    /// Single scattering `albedo`, and mean distance between two interactions, the same for all the channels
+    pub fn from_albedo(boundary: Box<dyn Hittable>, refraction_index: f64, roughness: f64, albedo: Color, mean_free_path: f64, g: f64) -> Box<dyn Hittable> {
+        let sigma_t = 1. / mean_free_path;
+        let sigma_a = Color::new(1. - albedo.r, 1. - albedo.g, 1. - albedo.b) * sigma_t;
+        Self::new(boundary, refraction_index, roughness, albedo * sigma_t, sigma_a, g)
+    }
+
+    fn channels(color: &Color) -> [f64; 3] {
+        [color.r, color.g, color.b]
+    }
+}
+
+impl Hittable for Subsurface {
+    fn bounding_box(&self) -> AABB {
+        self.boundary.bounding_box()
+    }
+
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        let interval = self.boundary.hit_intervals(ray).into_iter().find(|interval| interval.exit.t > ray_t.min)?;
+
+        // From outside, the ray refracts into the medium
+        if interval.enter.t > ray_t.min {
+            if interval.enter.t >= ray_t.max {
+                return None;
+            }
+            return Some(HitRecord { material: self.interface.clone(), ..interval.enter });
+        }
+
+        // From inside, it scatters in the medium, or refracts out when it gets to the boundary first
+        let mut rng = rand::thread_rng();
+        let sigma_t = Self::channels(&self.sigma_t);
+        let ray_length = ray.direction().length();
+        let distance = -(1. - rng.gen::<f64>()).ln() / sigma_t[rng.gen_range(0..3)];
+        let t = ray_t.min + distance / ray_length;
+        if t < interval.exit.t {
+            if t >= ray_t.max {
+                return None;
+            }
+
+            // sigma_s T(d) / the mean of the sigma_t T(d) densities
+            let transmittance = sigma_t.map(|sigma| (-sigma * distance).exp());
+            let density = (0..3).map(|c| sigma_t[c] * transmittance[c]).sum::<f64>() / 3.;
+            let sigma_s = Self::channels(&self.sigma_s);
+            let weight = Color::new(sigma_s[0] * transmittance[0], sigma_s[1] * transmittance[1], sigma_s[2] * transmittance[2]) * (1. / density);
+
+            let phase_function = HenyeyGreenstein::new(weight, self.g);
+            return Some(HitRecord::new(ray.at(t), *ray.direction(), t, Uv { u: 0., v: 0. }, ray, phase_function));
+        }
+
+        if interval.exit.t >= ray_t.max {
+            return None;
+        }
+
+        // T(d) / the mean probability of getting that far
+        let length = (interval.exit.t - ray_t.min) * ray_length;
+        let transmittance = sigma_t.map(|sigma| (-sigma * length).exp());
+        let probability = transmittance.iter().sum::<f64>() / 3.;
+        let weight = Color::new(transmittance[0], transmittance[1], transmittance[2]) * (1. / probability);
+        let material = Rc::new(Weighted { material: self.interface.clone(), weight });
+        Some(HitRecord { material, ..interval.exit })
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+/// Material scaling the attenuation of another one
+struct Weighted {
+    material: Rc<dyn Material>,
+    weight: Color,
+}
+
+impl Material for Weighted {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        let (attenuation, scattered) = self.material.scatter(ray_in, hit_record)?;
+        Some((attenuation * self.weight.clone(), scattered))
+    }
+
+    fn shading_normal(&self, hit_record: &HitRecord) -> Vec3 {
+        self.material.shading_normal(hit_record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Sphere;
+    use crate::material::Lambertian;
+    use crate::vector::Point;
+
+    // Mean light getting out of a unit sphere lit by a ray going through its center
+    fn escaping(object: &dyn Hittable, paths: usize) -> Color {
+        let mut total = Color::black();
+        for _ in 0..paths {
+            let mut ray = Ray::new(Point::new(0., 0., -5.), Vec3::new(0., 0., 1.), 0.);
+            let mut throughput = Color::white();
+            for _ in 0..10_000 {
+                match object.hit(&ray, &Interval::positive()) {
+                    None => {
+                        total += throughput;
+                        break;
+                    },
+                    Some(hit) => match hit.material.scatter(&ray, &hit) {
+                        Some((attenuation, scattered)) => {
+                            throughput *= attenuation;
+                            ray = scattered;
+                        },
+                        None => break,
+                    },
+                }
+            }
+        }
+        total * (1. / paths as f64)
+    }
+
+    #[test]
+    fn test_no_absorption_conserves_energy() {
+        let sphere = Sphere::new(Point::zero(), 1., Lambertian::new(Color::white()));
+        let object = Subsurface::from_albedo(sphere, 1.3, 0., Color::white(), 0.05, 0.5);
+        let escaped = escaping(&*object, 2000);
+        assert!((escaped.r - 1.).abs() < 0.01, "escaped: {:?}", escaped);
+    }
+
+    #[test]
+    fn test_absorption_follows_the_albedo() {
+        let sphere = Sphere::new(Point::zero(), 1., Lambertian::new(Color::white()));
+        let object = Subsurface::new(sphere, 1.3, 0., Color::new(9.9, 9., 5.), Color::new(0.1, 1., 5.), 0.);
+        let escaped = escaping(&*object, 2000);
+        assert!(escaped.r > escaped.g && escaped.g > escaped.b, "escaped: {:?}", escaped);
+        assert!(escaped.r < 1.);
+    }
+
+    #[test]
+    fn test_chromatic_extinction_matches_each_channel() {
+        let (sigma_s, sigma_a) = (Color::new(2., 5., 12.), Color::new(0.2, 0.5, 1.5));
+        let sphere = || Sphere::new(Point::zero(), 1., Lambertian::new(Color::white()));
+        let escaped = escaping(&*Subsurface::new(sphere(), 1.3, 0., sigma_s.clone(), sigma_a.clone(), 0.3), 20_000);
+
+        // Each channel alone, with a gray medium
+        let channels = [(sigma_s.r, sigma_a.r, escaped.r), (sigma_s.g, sigma_a.g, escaped.g), (sigma_s.b, sigma_a.b, escaped.b)];
+        for (scattering, absorption, escaped) in channels {
+            let gray = Subsurface::new(sphere(), 1.3, 0., Color::white() * scattering, Color::white() * absorption, 0.3);
+            let expected = escaping(&*gray, 20_000).r;
+            assert!((escaped - expected).abs() < 0.03, "escaped: {}, expected: {}", escaped, expected);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::vector::{Point, Vec3};
+use crate::hittable::{HitRecord, Hittable, HittableList, AABB, closest_on_outline};
+use crate::interval::Interval;
+use crate::ray::Ray;
+use crate::material::Material;
+use crate::mesh::PolygonMesh;
+use crate::texture::Uv;
+
+use std::rc::Rc;
+
+/// Vertex data shared by all the triangles of a mesh
+struct MeshData {
+    positions: Vec<Point>,
+    normals: Option<Vec<Vec3>>,
+    faces: Vec<[usize; 3]>,
+    uvs: Option<Vec<[Uv; 3]>>,
+}
+
+#[derive(Clone)]
+struct MeshTriangle {
+    mesh: Rc<MeshData>,
+    face: usize,
+    material: Rc<dyn Material>,
+    bbox: AABB,
+}
+
+/// Turns a polygon mesh into a list of triangles (polygons being triangulated), to be put in a BVH.
+/// With `smooth`, the shading normals are interpolated from the vertex normals.
+pub fn triangle_mesh(mesh: &PolygonMesh, smooth: bool, material: Rc<dyn Material>) -> HittableList {
+    let mesh = if mesh.is_triangle_mesh() { mesh.clone() } else { mesh.triangulate() };
+
+    let data = Rc::new(MeshData {
+        positions: mesh.positions.clone(),
+        normals: if smooth { Some(mesh.vertex_normals()) } else { None },
+        faces: mesh.faces.iter().map(|face| [face[0], face[1], face[2]]).collect(),
+        uvs: mesh.uvs.as_ref().map(|uvs| uvs.iter().map(|uv| [uv[0], uv[1], uv[2]]).collect()),
+    });
+
+    let mut objects = HittableList::new();
+    for (face, vertices) in data.faces.iter().enumerate() {
+        let [a, b, c] = vertices.map(|v| data.positions[v]);
+        objects += Box::new(MeshTriangle {
+            mesh: data.clone(),
+            face,
+            material: material.clone(),
+            bbox: AABB::from_points(a, b).surrounding_box(&AABB::from_points(c, c)).pad(),
+        }) as Box<dyn Hittable>;
+    }
+
+    objects
+}
+
+impl Hittable for MeshTriangle {
+    fn hit(&self, ray: &Ray, ray_t: &Interval) -> Option<HitRecord> {
+        // Möller–Trumbore
+        let vertices = self.mesh.faces[self.face];
+        let [a, b, c] = vertices.map(|v| self.mesh.positions[v]);
+        let edge1 = b - a;
+        let edge2 = c - a;
+
+        let p = ray.direction().cross(&edge2);
+        let det = edge1.dot(&p);
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let inv_det = 1. / det;
+
+        let s = *ray.origin() - a;
+        let beta = s.dot(&p) * inv_det;
+        if !(0. ..=1.).contains(&beta) {
+            return None;
+        }
+
+        let q = s.cross(&edge1);
+        let gamma = ray.direction().dot(&q) * inv_det;
+        if gamma < 0. || beta + gamma > 1. {
+            return None;
+        }
+
+        let t = edge2.dot(&q) * inv_det;
+        if !ray_t.surrounds(t) {
+            return None;
+        }
+
+        let alpha = 1. - beta - gamma;
+        let normal = match &self.mesh.normals {
+            Some(normals) => {
+                let [na, nb, nc] = vertices.map(|v| normals[v]);
+                alpha * na + beta * nb + gamma * nc
+            },
+            None => edge1.cross(&edge2),
+        };
+
+        let (uv, dpdu, dpdv) = match &self.mesh.uvs {
+            Some(uvs) => {
+                let [ua, ub, uc] = uvs[self.face];
+                let uv = Uv {
+                    u: alpha * ua.u + beta * ub.u + gamma * uc.u,
+                    v: alpha * ua.v + beta * ub.v + gamma * uc.v,
+                };
+
+                // Solve the edges for the derivatives along u and v
+                let (du1, dv1, du2, dv2) = (ub.u - ua.u, ub.v - ua.v, uc.u - ua.u, uc.v - ua.v);
+                let determinant = du1 * dv2 - dv1 * du2;
+                if determinant.abs() < 1e-12 {
+                    let (dpdu, dpdv) = edge1.cross(&edge2).normalized().orthonormal_basis();
+                    (uv, dpdu, dpdv)
+                } else {
+                    let dpdu = (dv2 * edge1 - dv1 * edge2) / determinant;
+                    let dpdv = (du1 * edge2 - du2 * edge1) / determinant;
+                    (uv, dpdu, dpdv)
+                }
+            },
+            None => (Uv { u: beta, v: gamma }, edge1, edge2),
+        };
+
+        Some(HitRecord::new(ray.at(t), normal, t, uv, ray, self.material.clone()).with_tangent_frame(dpdu, dpdv))
+    }
+
+    fn bounding_box(&self) -> AABB {
+        self.bbox
+    }
+
+    fn closest_edge(&self, point: &Point) -> Option<Point> {
+        let corners = self.mesh.faces[self.face].map(|v| self.mesh.positions[v]);
+        Some(closest_on_outline(point, &corners))
+    }
+
+    fn box_clone(&self) -> Box<dyn Hittable> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::Lambertian;
+    use crate::color::Color;
+
+    #[test]
+    fn test_hit_quad_mesh() {
+        let positions = vec![Point::new(0., 0., 0.), Point::new(2., 0., 0.), Point::new(2., 0., 2.), Point::new(0., 0., 2.)];
+        let uvs = vec![vec![Uv { u: 0., v: 0. }, Uv { u: 1., v: 0. }, Uv { u: 1., v: 1. }, Uv { u: 0., v: 1. }]];
+        let mesh = PolygonMesh::new(positions, vec![vec![0, 1, 2, 3]]).with_uvs(uvs);
+        let objects = triangle_mesh(&mesh, true, Lambertian::new(Color::white()));
+
+        let ray = Ray::new(Point::new(1.5, 3., 0.5), Vec3::new(0., -1., 0.), 0.);
+        let hit = objects.hit(&ray, &Interval::positive()).unwrap();
+        assert!((hit.t - 3.).abs() < 1e-9);
+        assert!((hit.normal - Vec3::new(0., 1., 0.)).length() < 1e-9);
+        assert!((hit.uv.u - 0.75).abs() < 1e-9);
+        assert!((hit.uv.v - 0.25).abs() < 1e-9);
+
+        let ray = Ray::new(Point::new(2.5, 3., 0.5), Vec3::new(0., -1., 0.), 0.);
+        assert!(objects.hit(&ray, &Interval::positive()).is_none());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::vector::{Point, Vec3};
+use crate::ray::Ray;
+use crate::implicit::{ImplicitFunction, Polynomial};
+
+use std::rc::Rc;
+
+/// Polynomial surface in x, y and z, made of terms `coefficient * x^i * y^j * z^k`
+pub struct AlgebraicSurface {
+    terms: Vec<(f64, [u32; 3])>,
+}
+
+impl AlgebraicSurface {
+    pub fn new(terms: Vec<(f64, [u32; 3])>) -> Rc<dyn ImplicitFunction> {
+        Rc::new(Self { terms })
+    }
+
+    pub fn sphere(radius: f64) -> Rc<dyn ImplicitFunction> {
+        Self::new(vec![
+            (1., [2, 0, 0]), (1., [0, 2, 0]), (1., [0, 0, 2]),
+            (-radius * radius, [0, 0, 0]),
+        ])
+    }
+
+    /// Torus lying in the xz plane: (x² + y² + z² + R² - r²)² - 4R²(x² + z²)
+    pub fn torus(major_radius: f64, minor_radius: f64) -> Rc<dyn ImplicitFunction> {
+        let r2 = major_radius * major_radius;
+        let k = r2 - minor_radius * minor_radius;
+        Self::new(vec![
+            (1., [4, 0, 0]), (1., [0, 4, 0]), (1., [0, 0, 4]),
+            (2., [2, 2, 0]), (2., [2, 0, 2]), (2., [0, 2, 2]),
+            (2. * k - 4. * r2, [2, 0, 0]), (2. * k, [0, 2, 0]), (2. * k - 4. * r2, [0, 0, 2]),
+            (k * k, [0, 0, 0]),
+        ])
+    }
+
+    /// Tanglecube: x⁴ - 5x² + y⁴ - 5y² + z⁴ - 5z² + 11.8
+    pub fn tanglecube() -> Rc<dyn ImplicitFunction> {
+        Self::new(vec![
+            (1., [4, 0, 0]), (-5., [2, 0, 0]),
+            (1., [0, 4, 0]), (-5., [0, 2, 0]),
+            (1., [0, 0, 4]), (-5., [0, 0, 2]),
+            (11.8, [0, 0, 0]),
+        ])
+    }
+
+    fn monomial(p: &Point, exponents: &[u32; 3]) -> f64 {
+        p.x().powi(exponents[0] as i32) * p.y().powi(exponents[1] as i32) * p.z().powi(exponents[2] as i32)
+    }
+}
+
+impl ImplicitFunction for AlgebraicSurface {
+    fn value(&self, p: &Point) -> f64 {
+        self.terms.iter().map(|(c, e)| c * Self::monomial(p, e)).sum()
+    }
+
+    fn gradient(&self, p: &Point) -> Vec3 {
+        let mut gradient = [0.; 3];
+        for (c, e) in &self.terms {
+            for (axis, g) in gradient.iter_mut().enumerate() {
+                if e[axis] == 0 {
+                    continue;
+                }
+                let mut derived = *e;
+                derived[axis] -= 1;
+                *g += c * e[axis] as f64 * Self::monomial(p, &derived);
+            }
+        }
+        Vec3::new(gradient[0], gradient[1], gradient[2])
+    }
+
+    fn along_ray(&self, ray: &Ray) -> Option<Polynomial> {
+        let origin = ray.origin();
+        let direction = ray.direction();
+        let axes = [0, 1, 2].map(|axis| Polynomial::linear(origin.axis(axis), direction.axis(axis)));
+
+        let polynomial = self.terms.iter().fold(Polynomial::constant(0.), |acc, (c, e)| {
+            let term = (0..3).fold(Polynomial::constant(*c), |term, axis| term.mul(&axes[axis].pow(e[axis])));
+            acc.add(&term)
+        });
+        Some(polynomial)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::vector::{Point, Vec3};
+use crate::implicit::ImplicitFunction;
+
+use std::rc::Rc;
+
+/// Blobby surface: iso-surface of the sum of the balls fields, using the Wyvill falloff (1 - d²/R²)³
+/// which vanishes beyond the radius R of each ball.
+pub struct Metaballs {
+    balls: Vec<(Point, f64)>,
+    threshold: f64,
+}
+
+impl Metaballs {
+    pub fn new(balls: Vec<(Point, f64)>, threshold: f64) -> Rc<dyn ImplicitFunction> {
+        Rc::new(Self { balls, threshold })
+    }
+}
+
+impl ImplicitFunction for Metaballs {
+    fn value(&self, p: &Point) -> f64 {
+        let field: f64 = self.balls.iter().map(|(center, radius)| {
+            let x = (*p - *center).length_squared() / (radius * radius);
+            if x >= 1. { 0. } else { (1. - x).powi(3) }
+        }).sum();
+
+        // Negative inside
+        self.threshold - field
+    }
+
+    fn gradient(&self, p: &Point) -> Vec3 {
+        let mut gradient = Vec3::zero();
+        for (center, radius) in &self.balls {
+            let offset = *p - *center;
+            let r2 = radius * radius;
+            let x = offset.length_squared() / r2;
+            if x < 1. {
+                gradient += (6. * (1. - x).powi(2) / r2) * offset;
+            }
+        }
+        gradient
+    }
+}
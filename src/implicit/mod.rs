@@ -0,0 +1,68 @@
+use crate::vector::{Point, Vec3};
+use crate::ray::Ray;
+
+use std::rc::Rc;
+
+mod polynomial;
+mod algebraic;
+mod metaballs;
+
+pub use polynomial::Polynomial;
+pub use algebraic::AlgebraicSurface;
+pub use metaballs::Metaballs;
+
+/// Surface defined by f(p) = 0, negative inside (so that the gradient points outward)
+pub trait ImplicitFunction {
+    fn value(&self, p: &Point) -> f64;
+
+    fn gradient(&self, p: &Point) -> Vec3 {
+        // Central differences
+        let h = 1e-5;
+        let dx = Vec3::new(h, 0., 0.);
+        let dy = Vec3::new(0., h, 0.);
+        let dz = Vec3::new(0., 0., h);
+        Vec3::new(
+            self.value(&(*p + dx)) - self.value(&(*p - dx)),
+            self.value(&(*p + dy)) - self.value(&(*p - dy)),
+            self.value(&(*p + dz)) - self.value(&(*p - dz)),
+        ) / (2. * h)
+    }
+
+    /// f(origin + t * direction) as a polynomial in t, when f is polynomial.
+    /// Allows exact root isolation with Sturm sequences instead of sampling the ray.
+    fn along_ray(&self, _ray: &Ray) -> Option<Polynomial> {
+        None
+    }
+}
+
+impl<F: Fn(&Point) -> f64> ImplicitFunction for F {
+    fn value(&self, p: &Point) -> f64 {
+        self(p)
+    }
+}
+
+pub struct Translated {
+    inner: Rc<dyn ImplicitFunction>,
+    offset: Vec3,
+}
+
+impl Translated {
+    pub fn new(inner: Rc<dyn ImplicitFunction>, offset: Vec3) -> Rc<dyn ImplicitFunction> {
+        Rc::new(Self { inner, offset })
+    }
+}
+
+impl ImplicitFunction for Translated {
+    fn value(&self, p: &Point) -> f64 {
+        self.inner.value(&(*p - self.offset))
+    }
+
+    fn gradient(&self, p: &Point) -> Vec3 {
+        self.inner.gradient(&(*p - self.offset))
+    }
+
+    fn along_ray(&self, ray: &Ray) -> Option<Polynomial> {
+        let local_ray = Ray::new(*ray.origin() - self.offset, *ray.direction(), ray.time());
+        self.inner.along_ray(&local_ray)
+    }
+}
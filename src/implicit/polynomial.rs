@@ -0,0 +1,227 @@
+use crate::interval::Interval;
+
+const EPSILON: f64 = 1e-12;
+const BISECTION_STEPS: usize = 64;
+
+/// Univariate polynomial, coefficients ordered by increasing degree
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<f64>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<f64>) -> Self {
+        let mut polynomial = Self { coefficients };
+        polynomial.trim();
+        polynomial
+    }
+
+    pub fn constant(value: f64) -> Self {
+        Self::new(vec![value])
+    }
+
+    /// a + b * t
+    pub fn linear(a: f64, b: f64) -> Self {
+        Self::new(vec![a, b])
+    }
+
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    pub fn eval(&self, t: f64) -> f64 {
+        self.coefficients.iter().rev().fold(0., |acc, c| acc * t + c)
+    }
+
+    pub fn derivative(&self) -> Self {
+        Self::new(self.coefficients.iter().enumerate().skip(1).map(|(i, c)| i as f64 * c).collect())
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        Self::new((0..len).map(|i| self.coefficient(i) + other.coefficient(i)).collect())
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::new(self.coefficients.iter().map(|c| c * factor).collect())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::new(Vec::new());
+        }
+
+        let mut coefficients = vec![0.; self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += a * b;
+            }
+        }
+        Self::new(coefficients)
+    }
+
+    pub fn pow(&self, exponent: u32) -> Self {
+        (0..exponent).fold(Self::constant(1.), |acc, _| acc.mul(self))
+    }
+
+    /// Remainder of the euclidean division by `divisor`
+    pub fn rem(&self, divisor: &Self) -> Self {
+        let mut remainder = self.coefficients.clone();
+        let divisor_degree = divisor.degree();
+        let leading = divisor.coefficients[divisor_degree];
+
+        while remainder.len() > divisor_degree && !remainder.is_empty() {
+            let shift = remainder.len() - 1 - divisor_degree;
+            let factor = remainder[remainder.len() - 1] / leading;
+            for (i, c) in divisor.coefficients.iter().enumerate() {
+                remainder[shift + i] -= factor * c;
+            }
+            remainder.pop();
+        }
+
+        Self::new(remainder)
+    }
+
+    /// Sturm sequence p, p', -rem(p, p'), ...
+    pub fn sturm_sequence(&self) -> Vec<Self> {
+        let mut sequence = vec![self.clone(), self.derivative()];
+        while !sequence[sequence.len() - 1].is_zero() && sequence[sequence.len() - 1].degree() > 0 {
+            let n = sequence.len();
+            let next = sequence[n - 2].rem(&sequence[n - 1]).scale(-1.);
+            if next.is_zero() {
+                break;
+            }
+            sequence.push(next);
+        }
+        sequence.retain(|p| !p.is_zero());
+        sequence
+    }
+
+    /// Distinct real roots sorted in increasing order, found in the open interval (with Sturm's theorem and bisection)
+    pub fn roots(&self, interval: &Interval) -> Vec<f64> {
+        if self.degree() == 0 {
+            return Vec::new();
+        }
+
+        let sequence = self.sturm_sequence();
+        let mut roots = Vec::new();
+        self.isolate_roots(&sequence, interval.min, interval.max, &mut roots, 0);
+        roots
+    }
+
+    fn isolate_roots(&self, sequence: &[Self], a: f64, b: f64, roots: &mut Vec<f64>, depth: usize) {
+        let count = Self::sign_changes(sequence, a).saturating_sub(Self::sign_changes(sequence, b));
+        if count == 0 {
+            return;
+        }
+
+        if count == 1 || depth >= BISECTION_STEPS {
+            if let Some(root) = self.bisect(a, b) {
+                roots.push(root);
+            }
+            return;
+        }
+
+        let mid = 0.5 * (a + b);
+        self.isolate_roots(sequence, a, mid, roots, depth + 1);
+        self.isolate_roots(sequence, mid, b, roots, depth + 1);
+    }
+
+    fn bisect(&self, mut a: f64, mut b: f64) -> Option<f64> {
+        let mut fa = self.eval(a);
+        let fb = self.eval(b);
+        if fa.abs() < EPSILON {
+            return Some(a);
+        }
+        if fb.abs() < EPSILON {
+            return Some(b);
+        }
+        if fa.signum() == fb.signum() {
+            // Root of even multiplicity: no sign change, the best guess is the middle
+            return Some(0.5 * (a + b));
+        }
+
+        for _ in 0..BISECTION_STEPS {
+            let mid = 0.5 * (a + b);
+            let fm = self.eval(mid);
+            if fm.signum() == fa.signum() {
+                a = mid;
+                fa = fm;
+            } else {
+                b = mid;
+            }
+        }
+        Some(0.5 * (a + b))
+    }
+
+    fn sign_changes(sequence: &[Self], t: f64) -> usize {
+        let mut changes = 0;
+        let mut last_sign = 0.;
+        for p in sequence {
+            let value = p.eval(t);
+            if value.abs() < EPSILON {
+                continue;
+            }
+            let sign = value.signum();
+            if last_sign != 0. && sign != last_sign {
+                changes += 1;
+            }
+            last_sign = sign;
+        }
+        changes
+    }
+
+    fn coefficient(&self, i: usize) -> f64 {
+        self.coefficients.get(i).cloned().unwrap_or(0.)
+    }
+
+    fn trim(&mut self) {
+        let scale = self.coefficients.iter().fold(0f64, |acc, c| acc.max(c.abs()));
+        while let Some(last) = self.coefficients.last() {
+            if last.abs() > EPSILON * scale.max(1.) {
+                break;
+            }
+            self.coefficients.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p = Polynomial::new(vec![1., 2., 3.]);
+        assert_eq!(p.eval(2.), 17.);
+        assert_eq!(p.derivative(), Polynomial::new(vec![2., 6.]));
+    }
+
+    #[test]
+    fn test_roots() {
+        // (t - 1)(t - 2)(t + 3)(t - 5)
+        let p = [1., 2., -3., 5.].iter().fold(Polynomial::constant(1.), |acc, r| acc.mul(&Polynomial::linear(-r, 1.)));
+        let roots = p.roots(&Interval::new(-10., 10.));
+        assert_eq!(roots.len(), 4);
+        for (root, expected) in roots.iter().zip([-3., 1., 2., 5.]) {
+            assert!((root - expected).abs() < 1e-9);
+        }
+
+        let roots = p.roots(&Interval::new(0., 3.));
+        assert_eq!(roots.len(), 2);
+    }
+
+    #[test]
+    fn test_no_roots() {
+        let p = Polynomial::new(vec![1., 0., 1.]);
+        assert!(p.roots(&Interval::new(-10., 10.)).is_empty());
+    }
+}
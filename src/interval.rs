@@ -17,6 +17,10 @@ impl Interval {
         Self::new(self.min.min(other.min), self.max.max(other.max))
     }
 
+    pub fn empty() -> Self {
+        Self::new(f64::INFINITY, -f64::INFINITY)
+    }
+
     pub fn positive() -> Self {
         Self::new(0.00001, f64::INFINITY)
     }
@@ -1,6 +1,7 @@
 pub mod color;
 pub mod vector;
 pub mod ray;
+pub mod ray_packet;
 pub mod writter;
 pub mod hittable;
 pub mod interval;
@@ -9,4 +10,10 @@ pub mod image_info;
 pub mod material;
 pub mod texture;
 pub mod terminal;
-pub mod noise;
\ No newline at end of file
+pub mod noise;
+pub mod sdf;
+pub mod implicit;
+pub mod mesh;
+pub mod transform;
+pub mod cache;
+pub mod statistics;
\ No newline at end of file
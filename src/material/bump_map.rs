@@ -0,0 +1,119 @@
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+use crate::texture::{Texture, Uv};
+
+use std::rc::Rc;
+
+/// Perturbs the shading normal of another material as if the surface was displaced along its normal
+/// by a grayscale height texture (times `strength`). Needs the dpdu/dpdv tangent frame of the hit record.
+pub struct BumpMap {
+    material: Rc<dyn Material>,
+    height: Rc<dyn Texture>,
+    strength: f64,
+}
+
+impl BumpMap {
+    pub fn new(material: Rc<dyn Material>, height: Rc<dyn Texture>, strength: f64) -> Rc<dyn Material> {
+        Rc::new(Self {
+            material,
+            height,
+            strength,
+        })
+    }
+
+    fn bumped(&self, hit_record: &HitRecord) -> HitRecord {
+        const DELTA: f64 = 0.0005;
+
+        let mut bumped = hit_record.clone();
+        if hit_record.dpdu.near_zero() || hit_record.dpdv.near_zero() {
+            return bumped;
+        }
+
+        // Finite differences of the height along u and v
+        let (uv, p) = (&hit_record.uv, &hit_record.point);
+        let height = self.height.scalar(uv, p);
+        let shifted_u = Uv { u: uv.u + DELTA, v: uv.v };
+        let shifted_v = Uv { u: uv.u, v: uv.v + DELTA };
+        let dh_du = (self.height.scalar(&shifted_u, &(*p + DELTA * hit_record.dpdu)) - height) / DELTA;
+        let dh_dv = (self.height.scalar(&shifted_v, &(*p + DELTA * hit_record.dpdv)) - height) / DELTA;
+
+        // Tangents of the shading normal (rather than of the geometry, which would undo smooth shading),
+        // displaced along the outward normal
+        let normal = hit_record.normal;
+        let outward = if hit_record.front_face { normal } else { -normal };
+        let tangent_u = hit_record.dpdu - normal * normal.dot(&hit_record.dpdu);
+        let tangent_v = hit_record.dpdv - normal * normal.dot(&hit_record.dpdv);
+        let dpdu = tangent_u + self.strength * dh_du * outward;
+        let dpdv = tangent_v + self.strength * dh_dv * outward;
+
+        // dpdu x dpdv, its tangent_u x tangent_v part being along the normal
+        let unbumped_normal = tangent_u.cross(&tangent_v);
+        let bumped_normal = unbumped_normal + self.strength * (dh_du * outward.cross(&tangent_v) - dh_dv * outward.cross(&tangent_u));
+        if unbumped_normal.near_zero() || bumped_normal.near_zero() {
+            return bumped;
+        }
+        let bumped_normal = bumped_normal.normalized();
+        let normal = if bumped_normal.dot(&normal) < 0. { -bumped_normal } else { bumped_normal };
+
+        bumped.normal = normal;
+        bumped.dpdu = dpdu;
+        bumped.dpdv = dpdv;
+        bumped
+    }
+}
+
+impl Material for BumpMap {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        self.material.scatter(ray_in, &self.bumped(hit_record))
+    }
+
+    fn emitted(&self, uv: &Uv, p: &Vec3) -> Color {
+        self.material.emitted(uv, p)
+    }
+
+    fn shading_normal(&self, hit_record: &HitRecord) -> Vec3 {
+        self.material.shading_normal(&self.bumped(hit_record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::{Heightfield, Hittable, triangle_mesh};
+    use crate::interval::Interval;
+    use crate::material::Lambertian;
+    use crate::mesh::PolygonMesh;
+    use crate::texture::NoiseTexture;
+    use crate::vector::Point;
+
+    fn assert_unchanged(object: &dyn Hittable, rays: &[Ray]) {
+        for ray in rays {
+            let hit = object.hit(ray, &Interval::positive()).unwrap();
+            let shading_normal = hit.material.shading_normal(&hit);
+            assert!((shading_normal - hit.normal).length() < 1e-9, "{:?} instead of {:?}", shading_normal, hit.normal);
+        }
+    }
+
+    #[test]
+    fn test_zero_strength_keeps_smooth_normals() {
+        let material = BumpMap::new(Lambertian::new(Color::white()), NoiseTexture::new(4.), 0.);
+        let rays = [
+            Ray::new(Point::new(0.3, 0.2, 5.), Vec3::new(0., 0., -1.), 0.),
+            Ray::new(Point::new(5., 0.4, -0.1), Vec3::new(-1., 0., 0.), 0.),
+        ];
+
+        let sphere = triangle_mesh(&PolygonMesh::sphere(Point::zero(), 1., 16, 8), true, material.clone());
+        assert_unchanged(&sphere, &rays);
+
+        let heights = (0..25).map(|i| ((i % 5) as f64 * 0.7).sin() + (i / 5) as f64 * 0.3).collect();
+        let terrain = Heightfield::new(heights, 5, 5, Point::new(-2., -1., -2.), Vec3::new(4., 1., 4.), material);
+        let rays = [
+            Ray::new(Point::new(0.3, 5., 0.2), Vec3::new(0., -1., 0.), 0.),
+            Ray::new(Point::new(-1.1, 5., 1.3), Vec3::new(0.1, -1., -0.2), 0.),
+        ];
+        assert_unchanged(&*terrain, &rays);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::material::Material;
+use crate::material::microfacet::{Ggx, ShadingFrame, fresnel_dielectric};
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+use crate::texture::Uv;
+
+use rand::Rng;
+use std::rc::Rc;
+
+/// Thin dielectric coat, like a varnish or a car paint clearcoat, over the outer side of any material.
+/// Light reflects on the coat with the Fresnel probability, else reaches the base, the light it scatters
+/// back out losing the part the coat reflects inwards.
+pub struct Coated {
+    base: Rc<dyn Material>,
+    refraction_index: f64,
+    distribution: Ggx,
+}
+
+impl Coated {
+    /// `roughness` of the coat in [0, 1], 0 being perfectly smooth
+    pub fn new(base: Rc<dyn Material>, refraction_index: f64, roughness: f64) -> Rc<dyn Material> {
+        Rc::new(Self {
+            base,
+            refraction_index,
+            distribution: Ggx::new(roughness, roughness),
+        })
+    }
+}
+
+impl Material for Coated {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        if !hit_record.front_face {
+            return self.base.scatter(ray_in, hit_record);
+        }
+
+        let frame = ShadingFrame::new(hit_record);
+        let wo = frame.to_local(&-ray_in.direction().normalized());
+        let mut rng = rand::thread_rng();
+        let m = if self.distribution.is_smooth() {
+            Vec3::new(0., 0., 1.)
+        } else {
+            self.distribution.sample_visible_normal(&wo, rng.gen(), rng.gen())
+        };
+
+        // Reflection on the coat, the Fresnel factor cancelling out with the probability
+        if rng.gen::<f64>() < fresnel_dielectric(wo.dot(&m), self.refraction_index) {
+            let wi = 2. * wo.dot(&m) * m - wo;
+            if wi.z() <= 0. {
+                return None;
+            }
+
+            let weight = if self.distribution.is_smooth() { 1. } else { self.distribution.g2(&wo, &wi) / self.distribution.g1(&wo) };
+            return Some((Color::white() * weight, Ray::new(hit_record.point, frame.to_world(&wi), ray_in.time())));
+        }
+
+        // Transmitted in and out, the transmittance being the same both ways
+        let (attenuation, scattered) = self.base.scatter(ray_in, hit_record)?;
+        let cos_out = scattered.direction().normalized().dot(&hit_record.normal);
+        let transmittance = if cos_out > 0. { 1. - fresnel_dielectric(cos_out, self.refraction_index) } else { 1. };
+        Some((attenuation * transmittance, scattered))
+    }
+
+    fn emitted(&self, uv: &Uv, p: &Vec3) -> Color {
+        self.base.emitted(uv, p)
+    }
+
+    fn shading_normal(&self, hit_record: &HitRecord) -> Vec3 {
+        self.base.shading_normal(hit_record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::Lambertian;
+    use crate::vector::Point;
+
+    fn albedo(material: Rc<dyn Material>, direction: Vec3) -> f64 {
+        let ray = Ray::new(Point::new(0., 0., 0.) - direction, direction, 0.);
+        let hit = HitRecord::new(Point::new(0., 0., 0.), Vec3::new(0., 0., 1.), 1., Uv { u: 0., v: 0. }, &ray, material.clone());
+        (0..20_000).filter_map(|_| material.scatter(&ray, &hit)).map(|(attenuation, _)| attenuation.g).sum::<f64>() / 20_000.
+    }
+
+    #[test]
+    fn test_coat_over_black() {
+        // Only the coat reflects: 4% at normal incidence, more at grazing angles
+        let coated = Coated::new(Lambertian::new(Color::black()), 1.5, 0.);
+        assert!((albedo(coated.clone(), Vec3::new(0., 0., -1.)) - 0.04).abs() < 0.01);
+        assert!(albedo(coated, Vec3::new(1., 0., -0.2)) > 0.2);
+    }
+
+    #[test]
+    fn test_coat_over_white_conserves_energy() {
+        let coated = Coated::new(Lambertian::new(Color::white()), 1.5, 0.3);
+        let albedo = albedo(coated, Vec3::new(1., 0., -1.));
+        assert!(albedo > 0.85 && albedo <= 1., "albedo: {}", albedo);
+    }
+}
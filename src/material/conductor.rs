@@ -0,0 +1,140 @@
+use crate::material::Material;
+use crate::material::microfacet::{Ggx, ShadingFrame, fresnel_conductor};
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+
+use rand::Rng;
+use std::rc::Rc;
+
+/// Rough metal, as a GGX microfacet surface reflecting with the Fresnel factor of a complex index of
+/// refraction eta + ik. Anisotropic roughness follows the dpdu / dpdv tangent frame of the hit record.
+pub struct Conductor {
+    eta: Color,
+    k: Color,
+    distribution: Ggx,
+}
+
+impl Conductor {
+    /// `roughness` in [0, 1], 0 being a perfect mirror
+    pub fn new(eta: Color, k: Color, roughness: f64) -> Rc<dyn Material> {
+        Self::anisotropic(eta, k, roughness, roughness)
+    }
+
+    /// Different roughnesses along the u and v directions of the surface, like brushed metal
+    pub fn anisotropic(eta: Color, k: Color, roughness_u: f64, roughness_v: f64) -> Rc<dyn Material> {
+        Rc::new(Self {
+            eta,
+            k,
+            distribution: Ggx::new(roughness_u, roughness_v),
+        })
+    }
+
+    /// Conductor whose reflectance is `reflectivity` at normal incidence and `edge_tint` near
+    /// grazing angles (Gulbrandsen 2014)
+    pub fn from_reflectivity(reflectivity: Color, edge_tint: Color, roughness: f64) -> Rc<dyn Material> {
+        let index = |r: f64, g: f64| {
+            let r = r.clamp(0., 0.999);
+            let eta = g * (1. - r) / (1. + r) + (1. - g) * (1. + r.sqrt()) / (1. - r.sqrt());
+            let k = ((r * (eta + 1.).powi(2) - (eta - 1.).powi(2)) / (1. - r)).max(0.).sqrt();
+            (eta, k)
+        };
+
+        let (eta_r, k_r) = index(reflectivity.r, edge_tint.r);
+        let (eta_g, k_g) = index(reflectivity.g, edge_tint.g);
+        let (eta_b, k_b) = index(reflectivity.b, edge_tint.b);
+        Self::new(Color::new(eta_r, eta_g, eta_b), Color::new(k_r, k_g, k_b), roughness)
+    }
+
+    // Measured indices at the red, green and blue wavelengths
+
+    pub fn gold(roughness: f64) -> Rc<dyn Material> {
+        Self::new(Color::new(0.143, 0.374, 1.442), Color::new(3.983, 2.385, 1.603), roughness)
+    }
+
+    pub fn copper(roughness: f64) -> Rc<dyn Material> {
+        Self::new(Color::new(0.200, 0.924, 1.102), Color::new(3.912, 2.452, 2.142), roughness)
+    }
+
+    pub fn aluminum(roughness: f64) -> Rc<dyn Material> {
+        Self::new(Color::new(1.657, 0.880, 0.521), Color::new(9.224, 6.270, 4.837), roughness)
+    }
+
+    pub fn silver(roughness: f64) -> Rc<dyn Material> {
+        Self::new(Color::new(0.155, 0.117, 0.138), Color::new(4.828, 3.122, 2.147), roughness)
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        let frame = ShadingFrame::new(hit_record);
+        let wo = frame.to_local(&-ray_in.direction().normalized());
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        if self.distribution.is_smooth() {
+            let wi = frame.to_world(&Vec3::new(-wo.x(), -wo.y(), wo.z()));
+            return Some((fresnel_conductor(wo.z(), &self.eta, &self.k), Ray::new(hit_record.point, wi, ray_in.time())));
+        }
+
+        // Reflect on a visible microfacet: the weight f cos / pdf boils down to F G2 / G1
+        let mut rng = rand::thread_rng();
+        let m = self.distribution.sample_visible_normal(&wo, rng.gen(), rng.gen());
+        let wi = 2. * wo.dot(&m) * m - wo;
+        if wi.z() <= 0. {
+            return None;
+        }
+
+        let weight = self.distribution.g2(&wo, &wi) / self.distribution.g1(&wo);
+        let attenuation = fresnel_conductor(wo.dot(&m), &self.eta, &self.k) * weight;
+        Some((attenuation, Ray::new(hit_record.point, frame.to_world(&wi), ray_in.time())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Quad;
+    use crate::interval::Interval;
+    use crate::vector::Point;
+
+    // Quad in the xy plane, u along x and v along y, hit by a ray coming from +z
+    fn scatter_many(material: Rc<dyn Material>, direction: Vec3) -> Vec<(Color, Vec3)> {
+        let quad = Quad::new(Point::new(-1., -1., 0.), Vec3::new(2., 0., 0.), Vec3::new(0., 2., 0.), material);
+        let ray = Ray::new(Point::new(0., 0., 1.) - direction, direction, 0.);
+        let hit = quad.hit(&ray, &Interval::positive()).unwrap();
+        (0..20_000)
+            .filter_map(|_| hit.material.scatter(&ray, &hit))
+            .map(|(attenuation, scattered)| (attenuation, scattered.direction().normalized()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rough_conductor_conserves_energy() {
+        let white = Color::new(0.999, 0.999, 0.999);
+        let samples = scatter_many(Conductor::from_reflectivity(white.clone(), white, 0.6), Vec3::new(1., 0., -1.));
+        let albedo = samples.iter().map(|(attenuation, _)| attenuation.g).sum::<f64>() / 20_000.;
+
+        // Single scattering loses some energy (about 20% here), more with the roughness, but never creates any
+        assert!(albedo > 0.75 && albedo <= 1., "albedo: {}", albedo);
+        assert!(samples.iter().all(|(_, direction)| direction.z() > 0.));
+    }
+
+    #[test]
+    fn test_anisotropic_spread() {
+        let samples = scatter_many(Conductor::anisotropic(Color::white(), Color::white() * 4., 0.7, 0.1), Vec3::new(0., 0., -1.));
+        let spread_u = samples.iter().map(|(_, direction)| direction.x().abs()).sum::<f64>();
+        let spread_v = samples.iter().map(|(_, direction)| direction.y().abs()).sum::<f64>();
+        assert!(spread_u > 5. * spread_v);
+    }
+
+    #[test]
+    fn test_gold_is_yellow() {
+        let samples = scatter_many(Conductor::gold(0.), Vec3::new(0., 0., -1.));
+        let (attenuation, direction) = &samples[0];
+        assert!((direction.z() - 1.).abs() < 1e-9);
+        assert!(attenuation.r > attenuation.g && attenuation.g > attenuation.b);
+    }
+}
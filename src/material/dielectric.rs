@@ -8,12 +8,26 @@ use std::rc::Rc;
 
 pub struct Dielectric {
     refraction_index: f64,
+    /// Absorption coefficient of the interior, per unit of length
+    absorption: Color,
 }
 
 impl Dielectric {
     pub fn new(refraction_index: f64) -> Rc<dyn Material> {
         Rc::new(Self {
             refraction_index,
+            absorption: Color::black(),
+        })
+    }
+
+    /// Tinted dielectric, light keeping the `color` fraction of its energy after traveling `distance`
+    /// inside the object (Beer-Lambert law), like colored glass, water or gemstones
+    pub fn with_absorption(refraction_index: f64, color: Color, distance: f64) -> Rc<dyn Material> {
+        let distance = distance.max(1e-6);
+        let coefficient = |transmittance: f64| -transmittance.clamp(1e-6, 1.).ln() / distance;
+        Rc::new(Self {
+            refraction_index,
+            absorption: Color::new(coefficient(color.r), coefficient(color.g), coefficient(color.b)),
         })
     }
 
@@ -44,7 +58,56 @@ impl Material for Dielectric {
             unit_direction.refract(&hit_record.normal, refraction_ratio)
         };
 
+        // Hitting the back face, the ray went through the interior
+        let attenuation = if hit_record.front_face {
+            Color::white()
+        } else {
+            let distance = hit_record.t * ray_in.direction().length();
+            Color::new((-self.absorption.r * distance).exp(), (-self.absorption.g * distance).exp(), (-self.absorption.b * distance).exp())
+        };
+
         let scattered = Ray::new(hit_record.point, ray_out, ray_in.time());
-        Some((Color::white(), scattered))
+        Some((attenuation, scattered))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Sphere;
+    use crate::interval::Interval;
+    use crate::vector::{Point, Vec3};
+
+    #[test]
+    fn test_absorption_along_the_path() {
+        // Halves the red every unit, through a diameter of 2 from the inside of a sphere
+        let glass = Dielectric::with_absorption(1.5, Color::new(0.5, 1., 1.), 1.);
+        let sphere = Sphere::new(Point::new(0., 0., 0.), 1., glass);
+        let ray = Ray::new(Point::new(0., 0., -1.), Vec3::new(0., 0., 2.), 0.);
+        let hit = sphere.hit(&ray, &Interval::new(0.001, f64::INFINITY)).unwrap();
+        assert!(!hit.front_face);
+
+        let (attenuation, _) = hit.material.scatter(&ray, &hit).unwrap();
+        assert!((attenuation.r - 0.25).abs() < 1e-9);
+        assert!((attenuation.g - 1.).abs() < 1e-9);
+
+        // Nothing is absorbed entering the object
+        let ray = Ray::new(Point::new(0., 0., -3.), Vec3::new(0., 0., 1.), 0.);
+        let hit = sphere.hit(&ray, &Interval::positive()).unwrap();
+        let (attenuation, _) = hit.material.scatter(&ray, &hit).unwrap();
+        assert_eq!(attenuation.r, 1.);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_absorption_over_no_distance() {
+        let glass = Dielectric::with_absorption(1.5, Color::new(0.5, 1., 1.), 0.);
+        let sphere = Sphere::new(Point::new(0., 0., 0.), 1., glass);
+        let ray = Ray::new(Point::new(0., 0., 0.), Vec3::new(0., 0., 1.), 0.);
+        let hit = sphere.hit(&ray, &Interval::positive()).unwrap();
+
+        // Clear channels stay clear, the others are absorbed
+        let (attenuation, _) = hit.material.scatter(&ray, &hit).unwrap();
+        assert_eq!(attenuation.g, 1.);
+        assert!(attenuation.r < 1e-9);
+    }
+}
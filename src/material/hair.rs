@@ -0,0 +1,330 @@
+use crate::material::Material;
+use crate::material::microfacet::fresnel_dielectric;
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+
+use rand::Rng;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+// Number of explicit lobes (R, TT, TRT), the last one gathering all the remaining scattering
+const P_MAX: usize = 3;
+const SQRT_PI_OVER_8: f64 = 0.626657069;
+
+/// Hair fiber scattering (d'Eon et al. / Chiang et al., as described in PBRT).
+/// It needs the hair direction in the hit record dpdu (as given by `Curve`), and the offset
+/// across the fiber in the v coordinate.
+pub struct Hair {
+    eta: f64,
+    sigma_a: Color,
+    v: [f64; P_MAX + 1],
+    s: f64,
+    sin_2k_alpha: [f64; 3],
+    cos_2k_alpha: [f64; 3],
+}
+
+impl Hair {
+    /// `sigma_a`: absorption inside the fiber, `beta_m` / `beta_n`: longitudinal / azimuthal roughness in [0, 1],
+    /// `alpha`: tilt of the cuticle scales, in degrees (usually 2°)
+    pub fn new(sigma_a: Color, beta_m: f64, beta_n: f64, alpha: f64) -> Rc<dyn Material> {
+        Rc::new(Self::build(sigma_a, beta_m, beta_n, alpha))
+    }
+
+    fn build(sigma_a: Color, beta_m: f64, beta_n: f64, alpha: f64) -> Self {
+        let beta_m = beta_m.clamp(0.01, 1.);
+        let beta_n = beta_n.clamp(0.01, 1.);
+
+        let v0 = (0.726 * beta_m + 0.812 * beta_m * beta_m + 3.7 * beta_m.powi(20)).powi(2);
+        let v = [v0, 0.25 * v0, 4. * v0, 4. * v0];
+        let s = SQRT_PI_OVER_8 * (0.265 * beta_n + 1.194 * beta_n * beta_n + 5.372 * beta_n.powi(22));
+
+        let mut sin_2k_alpha = [alpha.to_radians().sin(), 0., 0.];
+        let mut cos_2k_alpha = [(1. - sin_2k_alpha[0] * sin_2k_alpha[0]).max(0.).sqrt(), 0., 0.];
+        for i in 1..3 {
+            sin_2k_alpha[i] = 2. * cos_2k_alpha[i - 1] * sin_2k_alpha[i - 1];
+            cos_2k_alpha[i] = cos_2k_alpha[i - 1].powi(2) - sin_2k_alpha[i - 1].powi(2);
+        }
+
+        Self { eta: 1.55, sigma_a, v, s, sin_2k_alpha, cos_2k_alpha }
+    }
+
+    /// Absorption from the eumelanin (brown / black) and pheomelanin (red / blond) concentrations
+    pub fn from_melanin(eumelanin: f64, pheomelanin: f64, beta_m: f64, beta_n: f64) -> Rc<dyn Material> {
+        let sigma_a = Color::new(0.419, 0.697, 1.37) * eumelanin + Color::new(0.187, 0.4, 1.05) * pheomelanin;
+        Self::new(sigma_a, beta_m, beta_n, 2.)
+    }
+
+    /// Absorption giving roughly the requested color after multiple scattering
+    pub fn from_color(color: Color, beta_m: f64, beta_n: f64) -> Rc<dyn Material> {
+        let b = beta_n.clamp(0.01, 1.);
+        let denom = 5.969 - 0.215 * b + 2.532 * b.powi(2) - 10.73 * b.powi(3) + 5.574 * b.powi(4) + 0.245 * b.powi(5);
+        let sigma = |c: f64| (c.clamp(1e-4, 1.).ln() / denom).powi(2);
+        Self::new(Color::new(sigma(color.r), sigma(color.g), sigma(color.b)), beta_m, beta_n, 2.)
+    }
+
+    fn tilted(&self, p: usize, sin_theta_o: f64, cos_theta_o: f64) -> (f64, f64) {
+        // Account for the cuticle scales tilt, shifting each lobe differently
+        let (sin_theta_op, cos_theta_op) = match p {
+            0 => (
+                sin_theta_o * self.cos_2k_alpha[1] - cos_theta_o * self.sin_2k_alpha[1],
+                cos_theta_o * self.cos_2k_alpha[1] + sin_theta_o * self.sin_2k_alpha[1],
+            ),
+            1 => (
+                sin_theta_o * self.cos_2k_alpha[0] + cos_theta_o * self.sin_2k_alpha[0],
+                cos_theta_o * self.cos_2k_alpha[0] - sin_theta_o * self.sin_2k_alpha[0],
+            ),
+            2 => (
+                sin_theta_o * self.cos_2k_alpha[2] + cos_theta_o * self.sin_2k_alpha[2],
+                cos_theta_o * self.cos_2k_alpha[2] - sin_theta_o * self.sin_2k_alpha[2],
+            ),
+            _ => (sin_theta_o, cos_theta_o),
+        };
+        (sin_theta_op, cos_theta_op.abs())
+    }
+
+    /// Attenuation of each lobe, and the gamma_t angle inside the fiber
+    fn attenuations(&self, sin_theta_o: f64, cos_theta_o: f64, h: f64) -> ([Color; P_MAX + 1], f64) {
+        let sin_theta_t = sin_theta_o / self.eta;
+        let cos_theta_t = safe_sqrt(1. - sin_theta_t * sin_theta_t);
+
+        let etap = safe_sqrt(self.eta * self.eta - sin_theta_o * sin_theta_o) / cos_theta_o;
+        let sin_gamma_t = h / etap;
+        let cos_gamma_t = safe_sqrt(1. - sin_gamma_t * sin_gamma_t);
+        let gamma_t = sin_gamma_t.clamp(-1., 1.).asin();
+
+        let path = 2. * cos_gamma_t / cos_theta_t;
+        let transmittance = Color::new((-self.sigma_a.r * path).exp(), (-self.sigma_a.g * path).exp(), (-self.sigma_a.b * path).exp());
+
+        let cos_gamma_o = safe_sqrt(1. - h * h);
+        let f = fresnel_dielectric(cos_theta_o * cos_gamma_o, self.eta);
+
+        let mut ap = [Color::black(), Color::black(), Color::black(), Color::black()];
+        ap[0] = Color::white() * f;
+        ap[1] = transmittance.clone() * transmittance.clone() * ((1. - f) * (1. - f));
+        for p in 2..P_MAX {
+            ap[p] = ap[p - 1].clone() * transmittance.clone() * f;
+        }
+        let tf = transmittance.clone() * f;
+        let residual = Color::new(geometric_sum(tf.r), geometric_sum(tf.g), geometric_sum(tf.b));
+        ap[P_MAX] = ap[P_MAX - 1].clone() * tf * residual;
+
+        (ap, gamma_t)
+    }
+
+    /// f(wo, wi) * |cos(theta_i)| and the pdf of sampling wi, in the hair frame (x along the fiber)
+    fn evaluate(&self, wo: &Vec3, wi: &Vec3, h: f64) -> (Color, f64) {
+        let sin_theta_o = wo.x();
+        let cos_theta_o = safe_sqrt(1. - sin_theta_o * sin_theta_o);
+        let phi_o = wo.z().atan2(wo.y());
+
+        let sin_theta_i = wi.x();
+        let cos_theta_i = safe_sqrt(1. - sin_theta_i * sin_theta_i);
+        let phi_i = wi.z().atan2(wi.y());
+
+        let gamma_o = h.clamp(-1., 1.).asin();
+        let (ap, gamma_t) = self.attenuations(sin_theta_o, cos_theta_o, h);
+        let ap_pdf = lobe_pdf(&ap);
+        let phi = phi_i - phi_o;
+
+        let mut f = Color::black();
+        let mut pdf = 0.;
+        for p in 0..P_MAX {
+            let (sin_theta_op, cos_theta_op) = self.tilted(p, sin_theta_o, cos_theta_o);
+            let mp = longitudinal(cos_theta_i, cos_theta_op, sin_theta_i, sin_theta_op, self.v[p]);
+            let np = azimuthal(phi, p, self.s, gamma_o, gamma_t);
+            f += ap[p].clone() * (mp * np);
+            pdf += mp * ap_pdf[p] * np;
+        }
+
+        let mp = longitudinal(cos_theta_i, cos_theta_o, sin_theta_i, sin_theta_o, self.v[P_MAX]);
+        f += ap[P_MAX].clone() * (mp / (2. * PI));
+        pdf += mp * ap_pdf[P_MAX] / (2. * PI);
+
+        (f, pdf)
+    }
+
+    fn sample(&self, wo: &Vec3, h: f64) -> Vec3 {
+        let mut rng = rand::thread_rng();
+
+        let sin_theta_o = wo.x();
+        let cos_theta_o = safe_sqrt(1. - sin_theta_o * sin_theta_o);
+        let phi_o = wo.z().atan2(wo.y());
+
+        // Choose a lobe
+        let (ap, gamma_t) = self.attenuations(sin_theta_o, cos_theta_o, h);
+        let ap_pdf = lobe_pdf(&ap);
+        let mut u: f64 = rng.gen_range(0.0..1.0);
+        let mut p = P_MAX;
+        for (lobe, probability) in ap_pdf.iter().enumerate().take(P_MAX) {
+            if u < *probability {
+                p = lobe;
+                break;
+            }
+            u -= probability;
+        }
+
+        // Sample the longitudinal scattering
+        let (sin_theta_op, cos_theta_op) = self.tilted(p, sin_theta_o, cos_theta_o);
+        let u1: f64 = rng.gen_range(0.0..1.0f64).max(1e-5);
+        let cos_theta = 1. + self.v[p] * (u1 + (1. - u1) * (-2. / self.v[p]).exp()).ln();
+        let sin_theta = safe_sqrt(1. - cos_theta * cos_theta);
+        let cos_phi = (2. * PI * rng.gen_range(0.0..1.0)).cos();
+        let sin_theta_i = -cos_theta * sin_theta_op + sin_theta * cos_phi * cos_theta_op;
+        let cos_theta_i = safe_sqrt(1. - sin_theta_i * sin_theta_i);
+
+        // Sample the azimuthal scattering
+        let gamma_o = h.clamp(-1., 1.).asin();
+        let dphi = if p < P_MAX {
+            phi(p, gamma_o, gamma_t) + sample_trimmed_logistic(rng.gen_range(0.0..1.0), self.s, -PI, PI)
+        } else {
+            2. * PI * rng.gen_range(0.0..1.0)
+        };
+        let phi_i = phi_o + dphi;
+
+        Vec3::new(sin_theta_i, cos_theta_i * phi_i.cos(), cos_theta_i * phi_i.sin())
+    }
+}
+
+impl Material for Hair {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        // Hair frame: x along the fiber, y across its width, z facing the ray
+        let x = if hit_record.dpdu.near_zero() { hit_record.normal.orthonormal_basis().0 } else { hit_record.dpdu.normalized() };
+        let incoming = -ray_in.direction().normalized();
+        let facing = incoming - x * incoming.dot(&x);
+        let z_axis = if facing.near_zero() { x.orthonormal_basis().0 } else { facing.normalized() };
+        let y = z_axis.cross(&x);
+        let z = x.cross(&y);
+
+        let to_local = |w: &Vec3| Vec3::new(w.dot(&x), w.dot(&y), w.dot(&z));
+        let wo = to_local(&incoming);
+        let h = 2. * hit_record.uv.v - 1.;
+
+        let wi = self.sample(&wo, h);
+        let (f, pdf) = self.evaluate(&wo, &wi, h);
+        if pdf <= 0. || !pdf.is_finite() {
+            return None;
+        }
+
+        let direction = wi.x() * x + wi.y() * y + wi.z() * z;
+        let scattered = Ray::new(hit_record.point, direction, ray_in.time());
+        Some((f * (1. / pdf), scattered))
+    }
+}
+
+fn safe_sqrt(x: f64) -> f64 {
+    x.max(0.).sqrt()
+}
+
+fn geometric_sum(x: f64) -> f64 {
+    1. / (1. - x).max(1e-6)
+}
+
+fn lobe_pdf(ap: &[Color; P_MAX + 1]) -> [f64; P_MAX + 1] {
+    let luminance = |c: &Color| 0.2126 * c.r + 0.7152 * c.g + 0.0722 * c.b;
+    let sum: f64 = ap.iter().map(luminance).sum();
+    if sum <= 0. {
+        return [1., 0., 0., 0.];
+    }
+    ap.clone().map(|c| luminance(&c) / sum)
+}
+
+fn bessel_i0(x: f64) -> f64 {
+    let mut value = 0.;
+    let mut x2i = 1.;
+    let mut ifact = 1.;
+    let mut i4 = 1.;
+    for i in 0..10 {
+        if i > 1 {
+            ifact *= i as f64;
+        }
+        value += x2i / (i4 * ifact * ifact);
+        x2i *= x * x;
+        i4 *= 4.;
+    }
+    value
+}
+
+fn log_bessel_i0(x: f64) -> f64 {
+    if x > 12. {
+        x + 0.5 * (-(2. * PI).ln() + (1. / x).ln() + 1. / (8. * x))
+    } else {
+        bessel_i0(x).ln()
+    }
+}
+
+/// Longitudinal scattering function Mp
+fn longitudinal(cos_theta_i: f64, cos_theta_o: f64, sin_theta_i: f64, sin_theta_o: f64, v: f64) -> f64 {
+    let a = cos_theta_i * cos_theta_o / v;
+    let b = sin_theta_i * sin_theta_o / v;
+    if v <= 0.1 {
+        (log_bessel_i0(a) - b - 1. / v + std::f64::consts::LN_2 + (1. / (2. * v)).ln()).exp()
+    } else {
+        ((-b).exp() * bessel_i0(a)) / ((1. / v).sinh() * 2. * v)
+    }
+}
+
+fn phi(p: usize, gamma_o: f64, gamma_t: f64) -> f64 {
+    2. * p as f64 * gamma_t - 2. * gamma_o + p as f64 * PI
+}
+
+fn logistic(x: f64, s: f64) -> f64 {
+    let x = x.abs();
+    (-x / s).exp() / (s * (1. + (-x / s).exp()).powi(2))
+}
+
+fn logistic_cdf(x: f64, s: f64) -> f64 {
+    1. / (1. + (-x / s).exp())
+}
+
+fn trimmed_logistic(x: f64, s: f64, a: f64, b: f64) -> f64 {
+    logistic(x, s) / (logistic_cdf(b, s) - logistic_cdf(a, s))
+}
+
+fn sample_trimmed_logistic(u: f64, s: f64, a: f64, b: f64) -> f64 {
+    let k = logistic_cdf(b, s) - logistic_cdf(a, s);
+    let x = -s * (1. / (u * k + logistic_cdf(a, s)) - 1.).ln();
+    x.clamp(a, b)
+}
+
+/// Azimuthal scattering function Np
+fn azimuthal(phi_value: f64, p: usize, s: f64, gamma_o: f64, gamma_t: f64) -> f64 {
+    let mut dphi = phi_value - phi(p, gamma_o, gamma_t);
+    while dphi > PI {
+        dphi -= 2. * PI;
+    }
+    while dphi < -PI {
+        dphi += 2. * PI;
+    }
+    trimmed_logistic(dphi, s, -PI, PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_white_furnace() {
+        // Without absorption, the weights of the sampled directions average to 1 (energy conservation)
+        let hair = Hair::build(Color::black(), 0.3, 0.3, 2.);
+        let mut rng = rand::thread_rng();
+
+        let count = 20000;
+        let mut sum = 0.;
+        for _ in 0..count {
+            let theta_o: f64 = rng.gen_range(-1.2..1.2);
+            let wo = Vec3::new(theta_o.sin(), 0., theta_o.cos());
+            let h = rng.gen_range(-1.0..1.0);
+
+            let wi = hair.sample(&wo, h);
+            let (f, pdf) = hair.evaluate(&wo, &wi, h);
+            if pdf > 0. {
+                sum += f.g / pdf;
+            }
+        }
+
+        let average = sum / count as f64;
+        assert!((average - 1.).abs() < 0.05, "average weight {}", average);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+
+use rand::Rng;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+/// Phase function of participating media scattering mostly forward (`g` > 0), like skin or milk,
+/// or backward (`g` < 0). `g` = 0 is the `Isotropic` phase function.
+pub struct HenyeyGreenstein {
+    albedo: Color,
+    g: f64,
+}
+
+impl HenyeyGreenstein {
+    /// `g` in ]-1, 1[ is the mean cosine of the scattering angle
+    pub fn new(albedo: Color, g: f64) -> Rc<dyn Material> {
+        Rc::new(Self {
+            albedo,
+            g: g.clamp(-0.99, 0.99),
+        })
+    }
+
+    /// Cosine of the angle between the incoming and the scattered directions, sampled by inverting the CDF
+    fn sample_cos_theta(&self, u: f64) -> f64 {
+        if self.g.abs() < 1e-3 {
+            return 1. - 2. * u;
+        }
+
+        let g = self.g;
+        let term = (1. - g * g) / (1. - g + 2. * g * u);
+        ((1. + g * g - term * term) / (2. * g)).clamp(-1., 1.)
+    }
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        let mut rng = rand::thread_rng();
+        let cos_theta = self.sample_cos_theta(rng.gen());
+        let sin_theta = (1. - cos_theta * cos_theta).max(0.).sqrt();
+        let phi = 2. * PI * rng.gen::<f64>();
+
+        let direction = ray_in.direction().normalized();
+        let (u, v) = direction.orthonormal_basis();
+        let scattered = cos_theta * direction + sin_theta * (phi.cos() * u + phi.sin() * v);
+
+        // The phase function is its own sampling density
+        Some((self.albedo.clone(), Ray::new(hit_record.point, scattered, ray_in.time())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::Uv;
+    use crate::vector::{Point, Vec3};
+
+    #[test]
+    fn test_mean_cosine_is_g() {
+        for g in [-0.5, 0., 0.3, 0.9] {
+            let phase = HenyeyGreenstein::new(Color::white(), g);
+            let ray = Ray::new(Point::zero(), Vec3::new(0., 0., 2.), 0.);
+            let hit = HitRecord::new(Point::zero(), Vec3::new(1., 0., 0.), 0., Uv { u: 0., v: 0. }, &ray, phase.clone());
+
+            const SAMPLES: usize = 50_000;
+            let mean = (0..SAMPLES).map(|_| phase.scatter(&ray, &hit).unwrap().1.direction().normalized().z()).sum::<f64>() / SAMPLES as f64;
+            assert!((mean - g).abs() < 0.02, "g: {}, mean cosine: {}", g, mean);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+
+use rand::Rng;
+use std::f64::consts::PI;
+
+/// Below this alpha, microfacet surfaces are treated as perfectly smooth
+const SMOOTH_ALPHA: f64 = 1e-3;
+
+/// Orthonormal frame of the hit, the tangent following dpdu, in which the normal is +z
+pub struct ShadingFrame {
+    tangent: Vec3,
+    bitangent: Vec3,
+    normal: Vec3,
+}
+
+impl ShadingFrame {
+    pub fn new(hit_record: &HitRecord) -> Self {
+        let (tangent, bitangent) = hit_record.tangent_frame();
+        Self { tangent, bitangent, normal: hit_record.normal }
+    }
+
+    pub fn to_local(&self, v: &Vec3) -> Vec3 {
+        Vec3::new(v.dot(&self.tangent), v.dot(&self.bitangent), v.dot(&self.normal))
+    }
+
+    pub fn to_world(&self, v: &Vec3) -> Vec3 {
+        v.x() * self.tangent + v.y() * self.bitangent + v.z() * self.normal
+    }
+}
+
+/// GGX (Trowbridge-Reitz) distribution of microfacet normals, in the local frame of the surface,
+/// with the Smith height-correlated masking-shadowing
+#[derive(Clone, Copy, Debug)]
+pub struct Ggx {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+impl Ggx {
+    /// Roughnesses in [0, 1] along the tangent and the bitangent, squared into alphas
+    /// so that they feel linear
+    pub fn new(roughness_x: f64, roughness_y: f64) -> Self {
+        Self {
+            alpha_x: roughness_x.clamp(0., 1.).powi(2),
+            alpha_y: roughness_y.clamp(0., 1.).powi(2),
+        }
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        self.alpha_x.max(self.alpha_y) < SMOOTH_ALPHA
+    }
+
+    /// Smith auxiliary function, the ratio of the masked to the visible projected area of the microfacets
+    pub fn lambda(&self, w: &Vec3) -> f64 {
+        if w.z() == 0. {
+            return f64::INFINITY;
+        }
+
+        let (x, y) = (self.alpha_x * w.x(), self.alpha_y * w.y());
+        let tan2 = (x * x + y * y) / (w.z() * w.z());
+        0.5 * ((1. + tan2).sqrt() - 1.)
+    }
+
+    /// Fraction of the microfacets visible from w
+    pub fn g1(&self, w: &Vec3) -> f64 {
+        1. / (1. + self.lambda(w))
+    }
+
+    /// Fraction of the microfacets visible from both wo and wi
+    pub fn g2(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        1. / (1. + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Samples a microfacet normal among the ones visible from wo (wo.z > 0), with a density of
+    /// G1(wo) max(0, wo.m) D(m) / wo.z (Heitz 2018). `u1` and `u2` are uniform in [0, 1).
+    pub fn sample_visible_normal(&self, wo: &Vec3, u1: f64, u2: f64) -> Vec3 {
+        // Stretch the view to the hemisphere configuration
+        let v = Vec3::new(self.alpha_x * wo.x(), self.alpha_y * wo.y(), wo.z()).normalized();
+
+        let length_squared = v.x() * v.x() + v.y() * v.y();
+        let t1 = if length_squared > 0. { Vec3::new(-v.y(), v.x(), 0.) / length_squared.sqrt() } else { Vec3::new(1., 0., 0.) };
+        let t2 = v.cross(&t1);
+
+        // Uniform point on the disk, warped to the projected visible hemisphere
+        let r = u1.sqrt();
+        let phi = 2. * PI * u2;
+        let p1 = r * phi.cos();
+        let s = 0.5 * (1. + v.z());
+        let p2 = (1. - s) * (1. - p1 * p1).sqrt() + s * r * phi.sin();
+        let n = p1 * t1 + p2 * t2 + (1. - p1 * p1 - p2 * p2).max(0.).sqrt() * v;
+
+        // Unstretch
+        Vec3::new(self.alpha_x * n.x(), self.alpha_y * n.y(), n.z().max(0.)).normalized()
+    }
+}
+
+/// Unpolarized Fresnel reflectance of a conductor of complex index of refraction eta + ik,
+/// for each channel
+pub fn fresnel_conductor(cos_theta: f64, eta: &Color, k: &Color) -> Color {
+    let reflectance = |eta: f64, k: f64| {
+        let cos2 = cos_theta.clamp(0., 1.).powi(2);
+        let sin2 = 1. - cos2;
+
+        let t0 = eta * eta - k * k - sin2;
+        let a2_plus_b2 = (t0 * t0 + 4. * eta * eta * k * k).sqrt();
+        let t1 = a2_plus_b2 + cos2;
+        let a = (0.5 * (a2_plus_b2 + t0)).max(0.).sqrt();
+        let t2 = 2. * cos_theta.clamp(0., 1.) * a;
+        let rs = (t1 - t2) / (t1 + t2);
+
+        let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+        let t4 = t2 * sin2;
+        let rp = rs * (t3 - t4) / (t3 + t4);
+
+        0.5 * (rp + rs)
+    };
+
+    Color::new(reflectance(eta.r, k.r), reflectance(eta.g, k.g), reflectance(eta.b, k.b))
+}
+
+/// Unpolarized Fresnel reflectance of a dielectric interface, `eta` being the ratio of the index
+/// of refraction of the side the normal points away from over the other one. A negative `cos_theta_i`
+/// means that the light comes from that other side. 1 under total internal reflection.
+pub fn fresnel_dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+    let (cos_i, eta) = if cos_theta_i < 0. { (-cos_theta_i, 1. / eta) } else { (cos_theta_i, eta) };
+    let cos_i = cos_i.min(1.);
+    let sin2_t = (1. - cos_i * cos_i) / (eta * eta);
+    if sin2_t >= 1. {
+        return 1.;
+    }
+
+    let cos_t = (1. - sin2_t).sqrt();
+    let r_parallel = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    let r_perpendicular = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+    0.5 * (r_parallel * r_parallel + r_perpendicular * r_perpendicular)
+}
+
+/// Reflects or refracts wo (wo.z > 0) on a visible microfacet of a dielectric interface, picked with the
+/// Fresnel probabilities (Walter et al. 2007). Returns the direction, whether it went through the interface,
+/// and the weight of the sample, the Fresnel factor cancelling out.
+pub fn sample_dielectric(distribution: &Ggx, wo: &Vec3, eta: f64) -> Option<(Vec3, bool, f64)> {
+    let mut rng = rand::thread_rng();
+    let m = if distribution.is_smooth() {
+        Vec3::new(0., 0., 1.)
+    } else {
+        distribution.sample_visible_normal(wo, rng.gen(), rng.gen())
+    };
+
+    let cos_o = wo.dot(&m);
+    let (wi, refracted) = if rng.gen::<f64>() < fresnel_dielectric(cos_o, eta) {
+        let wi = 2. * cos_o * m - *wo;
+        if wi.z() <= 0. {
+            return None;
+        }
+        (wi, false)
+    } else {
+        let cos_t = (1. - (1. - cos_o * cos_o) / (eta * eta)).sqrt();
+        let wi = (cos_o / eta - cos_t) * m - *wo / eta;
+        if wi.z() >= 0. {
+            return None;
+        }
+        (wi, true)
+    };
+
+    let weight = if distribution.is_smooth() { 1. } else { distribution.g2(wo, &wi) / distribution.g1(wo) };
+    Some((wi, refracted, weight))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_normal_incidence_fresnel() {
+        // ((n - 1)^2 + k^2) / ((n + 1)^2 + k^2)
+        let (eta, k) = (Color::new(0.2, 1., 1.5), Color::new(3.9, 2., 0.));
+        let fresnel = fresnel_conductor(1., &eta, &k);
+        for (f, eta, k) in [(fresnel.r, eta.r, k.r), (fresnel.g, eta.g, k.g), (fresnel.b, eta.b, k.b)] {
+            let expected = ((eta - 1.).powi(2) + k * k) / ((eta + 1.).powi(2) + k * k);
+            assert!((f - expected).abs() < 1e-9);
+        }
+
+        // Everything is reflected at grazing angles
+        assert!((fresnel_conductor(0., &eta, &k).g - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dielectric_fresnel() {
+        // ((eta - 1) / (eta + 1))^2 at normal incidence, the same from both sides
+        assert!((fresnel_dielectric(1., 1.5) - 0.04).abs() < 1e-12);
+        assert!((fresnel_dielectric(1., 1. / 1.5) - 0.04).abs() < 1e-12);
+        assert!((fresnel_dielectric(0., 1.5) - 1.).abs() < 1e-12);
+
+        // Total internal reflection beyond the critical angle
+        let critical = (1. / 1.5f64).asin();
+        assert_eq!(fresnel_dielectric((critical + 0.01).cos(), 1. / 1.5), 1.);
+        assert!(fresnel_dielectric((critical - 0.01).cos(), 1. / 1.5) < 1.);
+
+        // From the other side of the interface
+        let cos = 0.3;
+        assert_eq!(fresnel_dielectric(-cos, 1.5), fresnel_dielectric(cos, 1. / 1.5));
+    }
+
+    #[test]
+    fn test_visible_normals_face_the_view() {
+        let ggx = Ggx::new(0.8, 0.3);
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..10_000 {
+            let wo = Vec3::new(rng.gen_range(-1.0..1.), rng.gen_range(-1.0..1.), rng.gen_range(0.01..1.)).normalized();
+            let m = ggx.sample_visible_normal(&wo, rng.gen_range(0.0..1.), rng.gen_range(0.0..1.));
+            assert!((m.length() - 1.).abs() < 1e-9);
+            assert!(m.z() >= 0.);
+            assert!(wo.dot(&m) >= -1e-9);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+use crate::texture::{Texture, Uv};
+
+use rand::Rng;
+use std::rc::Rc;
+
+/// Blend of two materials, each hit scattering with `b` with the probability read from the grayscale
+/// `factor` texture, else with `a`, like a rust mask over a metal
+pub struct Mix {
+    a: Rc<dyn Material>,
+    b: Rc<dyn Material>,
+    factor: Rc<dyn Texture>,
+}
+
+impl Mix {
+    pub fn new(a: Rc<dyn Material>, b: Rc<dyn Material>, factor: Rc<dyn Texture>) -> Rc<dyn Material> {
+        Rc::new(Self {
+            a,
+            b,
+            factor,
+        })
+    }
+
+    fn factor(&self, uv: &Uv, p: &Vec3) -> f64 {
+        self.factor.scalar(uv, p).clamp(0., 1.)
+    }
+}
+
+impl Material for Mix {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        if rand::thread_rng().gen::<f64>() < self.factor(&hit_record.uv, &hit_record.point) {
+            self.b.scatter(ray_in, hit_record)
+        } else {
+            self.a.scatter(ray_in, hit_record)
+        }
+    }
+
+    fn emitted(&self, uv: &Uv, p: &Vec3) -> Color {
+        self.a.emitted(uv, p).lerp(&self.b.emitted(uv, p), self.factor(uv, p))
+    }
+
+    /// The normal of the material with the largest share
+    fn shading_normal(&self, hit_record: &HitRecord) -> Vec3 {
+        if self.factor(&hit_record.uv, &hit_record.point) > 0.5 {
+            self.b.shading_normal(hit_record)
+        } else {
+            self.a.shading_normal(hit_record)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::{Lambertian, DiffuseLight};
+    use crate::texture::SolidColor;
+    use crate::vector::Point;
+
+    #[test]
+    fn test_mix_proportions() {
+        let mix = Mix::new(Lambertian::new(Color::red()), Lambertian::new(Color::blue()), SolidColor::gray(0.25));
+        let ray = Ray::new(Point::new(0., 0., 1.), Vec3::new(0., 0., -1.), 0.);
+        let hit = HitRecord::new(Point::new(0., 0., 0.), Vec3::new(0., 0., 1.), 1., Uv { u: 0., v: 0. }, &ray, mix.clone());
+
+        let blue = (0..10_000).filter(|_| mix.scatter(&ray, &hit).unwrap().0.b > 0.).count();
+        assert!((blue as f64 / 10_000. - 0.25).abs() < 0.02);
+
+        let glowing = Mix::new(DiffuseLight::white(4.), Lambertian::new(Color::red()), SolidColor::gray(0.25));
+        assert_eq!(glowing.emitted(&Uv { u: 0., v: 0. }, &Point::zero()).g, 3.);
+    }
+}
@@ -4,17 +4,36 @@ use crate::hittable::HitRecord;
 use crate::vector::Vec3;
 use crate::texture::Uv;
 
+mod bump_map;
+mod coated;
+mod conductor;
 mod diffuse_light;
 mod dielectric;
+mod hair;
+mod henyey_greenstein;
 mod isotropic;
 mod lambertian;
 mod metal;
+mod microfacet;
+mod mix;
+mod normal_map;
+mod principled;
+mod rough_dielectric;
 
+pub use bump_map::BumpMap;
+pub use coated::Coated;
+pub use conductor::Conductor;
 pub use diffuse_light::DiffuseLight;
 pub use dielectric::Dielectric;
+pub use hair::Hair;
+pub use henyey_greenstein::HenyeyGreenstein;
 pub use isotropic::Isotropic;
 pub use lambertian::Lambertian;
 pub use metal::Metal;
+pub use mix::Mix;
+pub use normal_map::NormalMap;
+pub use principled::{Principled, PrincipledParameters};
+pub use rough_dielectric::RoughDielectric;
 
 pub trait Material {
     fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)>;
@@ -22,4 +41,9 @@ pub trait Material {
     fn emitted(&self, _uv: &Uv, _p: &Vec3) -> Color {
         Color::black()
     }
+
+    /// Normal the material shades the hit with, for the materials perturbing the one of the hit record
+    fn shading_normal(&self, hit_record: &HitRecord) -> Vec3 {
+        hit_record.normal
+    }
 }
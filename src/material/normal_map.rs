@@ -0,0 +1,102 @@
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+use crate::texture::{Texture, Uv};
+
+use std::rc::Rc;
+
+/// Replaces the shading normal of another material by the one read from a tangent-space normal map,
+/// the red, green and blue channels mapping [0, 1] to [-1, 1] along dpdu, dpdv and the normal.
+pub struct NormalMap {
+    material: Rc<dyn Material>,
+    normals: Rc<dyn Texture>,
+}
+
+impl NormalMap {
+    pub fn new(material: Rc<dyn Material>, normals: Rc<dyn Texture>) -> Rc<dyn Material> {
+        Rc::new(Self {
+            material,
+            normals,
+        })
+    }
+
+    fn mapped(&self, hit_record: &HitRecord) -> HitRecord {
+        let mut mapped = hit_record.clone();
+
+        let value = self.normals.value(&hit_record.uv, &hit_record.point);
+        let (tangent, bitangent) = hit_record.tangent_frame();
+        let normal = (2. * value.r - 1.) * tangent + (2. * value.g - 1.) * bitangent + (2. * value.b - 1.) * hit_record.normal;
+
+        // Keep the geometric normal when the map points below the surface
+        if normal.dot(&hit_record.normal) > 0. {
+            mapped.normal = normal.normalized();
+        }
+        mapped
+    }
+}
+
+impl Material for NormalMap {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        self.material.scatter(ray_in, &self.mapped(hit_record))
+    }
+
+    fn emitted(&self, uv: &Uv, p: &Vec3) -> Color {
+        self.material.emitted(uv, p)
+    }
+
+    fn shading_normal(&self, hit_record: &HitRecord) -> Vec3 {
+        self.material.shading_normal(&self.mapped(hit_record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Quad;
+    use crate::interval::Interval;
+    use crate::material::{Metal, BumpMap};
+    use crate::texture::SolidColor;
+    use crate::vector::Point;
+
+    struct Ramp;
+
+    impl Texture for Ramp {
+        fn value(&self, uv: &Uv, _p: &Vec3) -> Color {
+            Color::white() * uv.u
+        }
+    }
+
+    fn reflect_on(material: Rc<dyn Material>) -> Vec3 {
+        // Mirror quad in the xy plane, u along x and v along y, hit straight on
+        let quad = Quad::new(Point::new(0., 0., 0.), Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.), material);
+        let ray = Ray::new(Point::new(0.5, 0.5, 1.), Vec3::new(0., 0., -1.), 0.);
+        let hit = quad.hit(&ray, &Interval::positive()).unwrap();
+        let (_, scattered) = hit.material.scatter(&ray, &hit).unwrap();
+        scattered.direction().normalized()
+    }
+
+    #[test]
+    fn test_flat_normal_map() {
+        let mirror = Metal::new(Color::white(), 0.);
+        let direction = reflect_on(NormalMap::new(mirror, SolidColor::from_rgb(0.5, 0.5, 1.)));
+        assert!((direction - Vec3::new(0., 0., 1.)).length() < 1e-9);
+    }
+
+    #[test]
+    fn test_tilted_normal_map() {
+        // Normal tilted by 45 degrees towards u, the reflection goes along u
+        let mirror = Metal::new(Color::white(), 0.);
+        let direction = reflect_on(NormalMap::new(mirror, SolidColor::from_rgb(1., 0.5, 1.)));
+        assert!((direction - Vec3::new(1., 0., 0.)).length() < 1e-9);
+    }
+
+    #[test]
+    fn test_bump_map_slope() {
+        // Height rising along u with a slope of 1, the normal leans against it by 45 degrees
+        let mirror = Metal::new(Color::white(), 0.);
+        let direction = reflect_on(BumpMap::new(mirror, Rc::new(Ramp), 1.));
+        assert!((direction - Vec3::new(-1., 0., 0.)).length() < 1e-6);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::material::Material;
+use crate::material::microfacet::{Ggx, ShadingFrame, sample_dielectric};
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::vector::Vec3;
+use crate::texture::{Texture, SolidColor};
+
+use rand::Rng;
+use std::f64::consts::PI;
+use std::rc::Rc;
+
+/// Parameters of the `Principled` material, all in [0, 1] but the index of refraction.
+/// Scalar parameters are read from the average of the texture channels.
+#[derive(Clone)]
+pub struct PrincipledParameters {
+    pub base_color: Rc<dyn Texture>,
+    /// Blends from a dielectric to a conductor reflecting the base color
+    pub metallic: Rc<dyn Texture>,
+    pub roughness: Rc<dyn Texture>,
+    /// Reflectance of the dielectric at normal incidence, 0.5 being 4%
+    pub specular: Rc<dyn Texture>,
+    /// Tints the dielectric reflection towards the base color
+    pub specular_tint: Rc<dyn Texture>,
+    /// Soft reflection at grazing angles, for cloth
+    pub sheen: Rc<dyn Texture>,
+    /// Second, white and glossy specular layer, like a varnish
+    pub clearcoat: Rc<dyn Texture>,
+    pub clearcoat_gloss: Rc<dyn Texture>,
+    /// Blends from an opaque dielectric to a rough glass tinted by the base color
+    pub transmission: Rc<dyn Texture>,
+    pub ior: Rc<dyn Texture>,
+    /// Stretches the highlights along the u direction of the surface
+    pub anisotropic: Rc<dyn Texture>,
+}
+
+impl Default for PrincipledParameters {
+    fn default() -> Self {
+        Self {
+            base_color: SolidColor::gray(0.8),
+            metallic: SolidColor::gray(0.),
+            roughness: SolidColor::gray(0.5),
+            specular: SolidColor::gray(0.5),
+            specular_tint: SolidColor::gray(0.),
+            sheen: SolidColor::gray(0.),
+            clearcoat: SolidColor::gray(0.),
+            clearcoat_gloss: SolidColor::gray(1.),
+            transmission: SolidColor::gray(0.),
+            ior: SolidColor::gray(1.5),
+            anisotropic: SolidColor::gray(0.),
+        }
+    }
+}
+
+/// Disney principled BSDF (Burley 2012 and 2015): a diffuse base with sheen, a GGX specular lobe, a rough
+/// glass transmission and a clearcoat, blended by artist-friendly parameters. A single lobe is sampled at
+/// each scattering, picked with a probability following its weight.
+pub struct Principled {
+    parameters: PrincipledParameters,
+}
+
+/// Parameters read from the textures at the hit point
+struct Sample {
+    base_color: Color,
+    metallic: f64,
+    roughness: f64,
+    specular: f64,
+    specular_tint: f64,
+    sheen: f64,
+    clearcoat: f64,
+    clearcoat_gloss: f64,
+    transmission: f64,
+    ior: f64,
+    anisotropic: f64,
+}
+
+impl Principled {
+    pub fn new(parameters: PrincipledParameters) -> Rc<dyn Material> {
+        Rc::new(Self {
+            parameters,
+        })
+    }
+
+    fn sample(&self, hit_record: &HitRecord) -> Sample {
+        let (uv, p) = (&hit_record.uv, &hit_record.point);
+        let scalar = |texture: &Rc<dyn Texture>| texture.scalar(uv, p).clamp(0., 1.);
+        let parameters = &self.parameters;
+
+        Sample {
+            base_color: parameters.base_color.value(uv, p),
+            metallic: scalar(&parameters.metallic),
+            roughness: scalar(&parameters.roughness),
+            specular: scalar(&parameters.specular),
+            specular_tint: scalar(&parameters.specular_tint),
+            sheen: scalar(&parameters.sheen),
+            clearcoat: scalar(&parameters.clearcoat),
+            clearcoat_gloss: scalar(&parameters.clearcoat_gloss),
+            transmission: scalar(&parameters.transmission),
+            ior: parameters.ior.scalar(uv, p).max(1.),
+            anisotropic: scalar(&parameters.anisotropic),
+        }
+    }
+}
+
+impl Sample {
+    /// Hue and saturation of the base color, at a luminance of 1
+    fn tint(&self) -> Color {
+        let luminance = 0.3 * self.base_color.r + 0.6 * self.base_color.g + 0.1 * self.base_color.b;
+        if luminance > 0. { self.base_color.clone() * (1. / luminance) } else { Color::white() }
+    }
+
+    /// Reflectance of the specular lobe at normal incidence
+    fn specular_color(&self) -> Color {
+        let dielectric = Color::white().lerp(&self.tint(), self.specular_tint) * (0.08 * self.specular);
+        dielectric.lerp(&self.base_color, self.metallic)
+    }
+
+    fn distribution(&self) -> Ggx {
+        // Alphas of roughness^2 / aspect and roughness^2 * aspect
+        let aspect = (1. - 0.9 * self.anisotropic).sqrt();
+        Ggx::new(self.roughness / aspect.sqrt(), self.roughness * aspect.sqrt())
+    }
+
+    /// Cosine-weighted sample of the Disney diffuse and sheen, and its weight
+    fn diffuse(&self, wo: &Vec3) -> (Color, Vec3) {
+        let mut rng = rand::thread_rng();
+        let (u1, u2): (f64, f64) = (rng.gen(), rng.gen());
+        let r = u1.sqrt();
+        let wi = Vec3::new(r * (2. * PI * u2).cos(), r * (2. * PI * u2).sin(), (1. - u1).max(0.).sqrt());
+
+        let h = *wo + wi;
+        let cos_d = if h.near_zero() { 1. } else { wi.dot(&h.normalized()) };
+        let fd90 = 0.5 + 2. * self.roughness * cos_d * cos_d;
+        let fd = (1. + (fd90 - 1.) * schlick_weight(wi.z())) * (1. + (fd90 - 1.) * schlick_weight(wo.z()));
+
+        // f cos / pdf, with a pdf of cos / pi
+        let sheen = Color::white().lerp(&self.tint(), 0.5) * (self.sheen * schlick_weight(cos_d) * PI);
+        (self.base_color.clone() * fd + sheen, wi)
+    }
+
+    /// Reflection on a visible microfacet, with the Schlick Fresnel of the specular color
+    fn specular(&self, wo: &Vec3) -> Option<(Color, Vec3)> {
+        let distribution = self.distribution();
+        let m = if distribution.is_smooth() {
+            Vec3::new(0., 0., 1.)
+        } else {
+            let mut rng = rand::thread_rng();
+            distribution.sample_visible_normal(wo, rng.gen(), rng.gen())
+        };
+        let wi = 2. * wo.dot(&m) * m - *wo;
+        if wi.z() <= 0. {
+            return None;
+        }
+
+        let fresnel = self.specular_color().lerp(&Color::white(), schlick_weight(wi.dot(&m)));
+        let weight = if distribution.is_smooth() { 1. } else { distribution.g2(wo, &wi) / distribution.g1(wo) };
+        Some((fresnel * weight, wi))
+    }
+
+    /// Rough glass, the base color tinting the light on each crossing of the interface
+    fn transmission(&self, wo: &Vec3, front_face: bool) -> Option<(Color, Vec3)> {
+        let eta = if front_face { self.ior } else { 1. / self.ior };
+        let (wi, refracted, weight) = sample_dielectric(&self.distribution(), wo, eta)?;
+
+        let tint = if refracted {
+            Color::new(self.base_color.r.sqrt(), self.base_color.g.sqrt(), self.base_color.b.sqrt())
+        } else {
+            Color::white()
+        };
+        Some((tint * weight, wi))
+    }
+
+    /// GTR1 clearcoat, of index of refraction 1.5, sampled by its distribution of normals
+    fn clearcoat(&self, wo: &Vec3) -> Option<(Color, Vec3)> {
+        let alpha = 0.1 + (0.001 - 0.1) * self.clearcoat_gloss;
+        let alpha2 = alpha * alpha;
+
+        let mut rng = rand::thread_rng();
+        let (u1, u2): (f64, f64) = (rng.gen(), rng.gen());
+        let cos_h = ((1. - alpha2.powf(1. - u1)) / (1. - alpha2)).max(0.).sqrt();
+        let sin_h = (1. - cos_h * cos_h).max(0.).sqrt();
+        let h = Vec3::new(sin_h * (2. * PI * u2).cos(), sin_h * (2. * PI * u2).sin(), cos_h);
+
+        let cos_o = wo.dot(&h);
+        let wi = 2. * cos_o * h - *wo;
+        if cos_o <= 0. || wi.z() <= 0. {
+            return None;
+        }
+
+        // f cos / pdf, the distribution cancelling out with a pdf of D cos_h / (4 cos_o)
+        let fresnel = 0.04 + 0.96 * schlick_weight(wi.dot(&h));
+        let masking = Ggx::new(0.5, 0.5);
+        let weight = fresnel * masking.g1(wo) * masking.g1(&wi) * cos_o / (wo.z() * cos_h);
+        Some((Color::white() * weight, wi))
+    }
+}
+
+fn schlick_weight(cos: f64) -> f64 {
+    (1. - cos.clamp(0., 1.)).powi(5)
+}
+
+impl Material for Principled {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        let frame = ShadingFrame::new(hit_record);
+        let wo = frame.to_local(&-ray_in.direction().normalized());
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        // Lobes weights, the transmission taking over the dielectric reflection
+        let sample = self.sample(hit_record);
+        let dielectric = 1. - sample.metallic;
+        let weights = [
+            dielectric * (1. - sample.transmission),
+            1. - dielectric * sample.transmission,
+            dielectric * sample.transmission,
+            0.25 * sample.clearcoat,
+        ];
+        let total: f64 = weights.iter().sum();
+
+        // Pick a lobe with a probability of its weight over the total: the weight of the sample is the total
+        let mut choice = rand::thread_rng().gen::<f64>() * total;
+        let mut lobe = 0;
+        while lobe < weights.len() - 1 && choice >= weights[lobe] {
+            choice -= weights[lobe];
+            lobe += 1;
+        }
+
+        let (attenuation, wi) = match lobe {
+            0 => Some(sample.diffuse(&wo)),
+            1 => sample.specular(&wo),
+            2 => sample.transmission(&wo, hit_record.front_face),
+            _ => sample.clearcoat(&wo),
+        }?;
+
+        Some((attenuation * total, Ray::new(hit_record.point, frame.to_world(&wi), ray_in.time())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Quad;
+    use crate::interval::Interval;
+    use crate::vector::Point;
+
+    // Mean attenuation of the light scattered above and below a quad in the xy plane, hit from +z at 45 degrees
+    fn albedo(parameters: PrincipledParameters) -> (Color, Color) {
+        const SAMPLES: usize = 20_000;
+        let quad = Quad::new(Point::new(-1., -1., 0.), Vec3::new(2., 0., 0.), Vec3::new(0., 2., 0.), Principled::new(parameters));
+        let direction = Vec3::new(1., 0., -1.);
+        let ray = Ray::new(Point::new(0., 0., 0.) - direction, direction, 0.);
+        let hit = quad.hit(&ray, &Interval::positive()).unwrap();
+
+        let (mut reflected, mut transmitted) = (Color::black(), Color::black());
+        for _ in 0..SAMPLES {
+            if let Some((attenuation, scattered)) = hit.material.scatter(&ray, &hit) {
+                if scattered.direction().z() > 0. { reflected += attenuation } else { transmitted += attenuation }
+            }
+        }
+        (reflected * (1. / SAMPLES as f64), transmitted * (1. / SAMPLES as f64))
+    }
+
+    #[test]
+    fn test_white_diffuse() {
+        let (reflected, transmitted) = albedo(PrincipledParameters { base_color: SolidColor::gray(1.), roughness: SolidColor::gray(1.), ..Default::default() });
+
+        // Disney diffuse is close to Lambertian, plus a few percent of specular reflection
+        assert!(reflected.g > 0.9 && reflected.g < 1.2, "reflected: {:?}", reflected);
+        assert_eq!(transmitted.g, 0.);
+    }
+
+    #[test]
+    fn test_metal_reflects_base_color() {
+        let gold = SolidColor::from_rgb(1., 0.8, 0.3);
+        let (reflected, _) = albedo(PrincipledParameters { base_color: gold, metallic: SolidColor::gray(1.), roughness: SolidColor::gray(0.), ..Default::default() });
+
+        // Schlick Fresnel of the base color at 45 degrees
+        let expected = 0.3 + 0.7 * (1. - 0.5f64.sqrt()).powi(5);
+        assert!((reflected.b - expected).abs() < 1e-9);
+        assert!((reflected.r - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_glass_transmits() {
+        let (reflected, transmitted) = albedo(PrincipledParameters { transmission: SolidColor::gray(1.), base_color: SolidColor::gray(1.), roughness: SolidColor::gray(0.2), ..Default::default() });
+        assert!(transmitted.g > 0.85 && reflected.g < 0.15, "reflected: {:?}, transmitted: {:?}", reflected, transmitted);
+    }
+}
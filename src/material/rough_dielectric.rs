@@ -0,0 +1,95 @@
+use crate::material::Material;
+use crate::material::microfacet::{Ggx, ShadingFrame, sample_dielectric};
+use crate::ray::Ray;
+use crate::hittable::HitRecord;
+use crate::color::Color;
+use crate::texture::{Texture, SolidColor};
+
+use std::rc::Rc;
+
+/// Frosted glass: a GGX microfacet interface reflecting and refracting on its microfacets with the exact
+/// Fresnel factor (Walter et al. 2007), the microfacets being sampled among the visible ones
+pub struct RoughDielectric {
+    refraction_index: f64,
+    roughness: Rc<dyn Texture>,
+}
+
+impl RoughDielectric {
+    /// `roughness` in [0, 1], 0 being the smooth `Dielectric`
+    pub fn new(refraction_index: f64, roughness: f64) -> Rc<dyn Material> {
+        Self::from_texture(refraction_index, SolidColor::gray(roughness))
+    }
+
+    /// Roughness read from a grayscale texture
+    pub fn from_texture(refraction_index: f64, roughness: Rc<dyn Texture>) -> Rc<dyn Material> {
+        Rc::new(Self {
+            refraction_index,
+            roughness,
+        })
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<(Color, Ray)> {
+        // The normal faces the ray: entering the object from the front face, leaving it from the back one
+        let eta = if hit_record.front_face { self.refraction_index } else { 1. / self.refraction_index };
+
+        let frame = ShadingFrame::new(hit_record);
+        let wo = frame.to_local(&-ray_in.direction().normalized());
+        if wo.z() <= 0. {
+            return None;
+        }
+
+        let roughness = self.roughness.scalar(&hit_record.uv, &hit_record.point);
+        let (wi, _, weight) = sample_dielectric(&Ggx::new(roughness, roughness), &wo, eta)?;
+        Some((Color::white() * weight, Ray::new(hit_record.point, frame.to_world(&wi), ray_in.time())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittable::Quad;
+    use crate::interval::Interval;
+    use crate::vector::{Point, Vec3};
+
+    #[test]
+    fn test_rough_glass_transmits_and_reflects() {
+        // Glass slab surface in the xy plane, hit from +z at 30 degrees
+        let quad = Quad::new(Point::new(-1., -1., 0.), Vec3::new(2., 0., 0.), Vec3::new(0., 2., 0.), RoughDielectric::new(1.5, 0.4));
+        let direction = Vec3::new(0.5, 0., -(3f64.sqrt()) / 2.);
+        let ray = Ray::new(Point::new(0., 0., 0.) - direction, direction, 0.);
+        let hit = quad.hit(&ray, &Interval::positive()).unwrap();
+
+        let (mut reflected, mut transmitted, mut energy) = (0, 0, 0.);
+        for _ in 0..20_000 {
+            if let Some((attenuation, scattered)) = hit.material.scatter(&ray, &hit) {
+                assert!(attenuation.r <= 1.);
+                energy += attenuation.r;
+                if scattered.direction().z() > 0. { reflected += 1 } else { transmitted += 1 }
+            }
+        }
+
+        // About 4% reflected, nearly nothing lost going into the denser medium
+        let reflectance = reflected as f64 / (reflected + transmitted) as f64;
+        assert!(reflectance > 0.02 && reflectance < 0.1, "reflectance: {}", reflectance);
+        assert!(energy / 20_000. > 0.9);
+    }
+
+    #[test]
+    fn test_smooth_refraction_follows_snell() {
+        let quad = Quad::new(Point::new(-1., -1., 0.), Vec3::new(2., 0., 0.), Vec3::new(0., 2., 0.), RoughDielectric::new(1.5, 0.));
+        let direction = Vec3::new(0.5, 0., -(3f64.sqrt()) / 2.);
+        let ray = Ray::new(Point::new(0., 0., 0.) - direction, direction, 0.);
+        let hit = quad.hit(&ray, &Interval::positive()).unwrap();
+
+        for _ in 0..100 {
+            let (_, scattered) = hit.material.scatter(&ray, &hit).unwrap();
+            let direction = scattered.direction().normalized();
+            if direction.z() < 0. {
+                // sin(30°) = 1.5 sin(theta_t)
+                assert!((direction.x() - 0.5 / 1.5).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::cache::{CacheHasher, CacheReader, CacheWriter};
+use crate::mesh::PolygonMesh;
+use crate::vector::Point;
+use crate::texture::Uv;
+
+use std::hash::Hasher;
+use std::io::Error;
+
+const KIND: &[u8; 4] = b"MESH";
+
+impl PolygonMesh {
+    /// Feeds the whole mesh to the hasher: positions, faces, texture coordinates and creases
+    pub fn hash_geometry(&self, hasher: &mut CacheHasher) {
+        hasher.write_usize(self.positions.len());
+        for position in &self.positions {
+            hasher.write_f64(position.x());
+            hasher.write_f64(position.y());
+            hasher.write_f64(position.z());
+        }
+
+        hasher.write_usize(self.faces.len());
+        for face in &self.faces {
+            hasher.write_usize(face.len());
+            face.iter().for_each(|&vertex| hasher.write_usize(vertex));
+        }
+
+        hasher.write_u8(self.uvs.is_some() as u8);
+        for uv in self.uvs.iter().flatten().flatten() {
+            hasher.write_f64(uv.u);
+            hasher.write_f64(uv.v);
+        }
+
+        let creases = self.sorted_creases();
+        hasher.write_usize(creases.len());
+        for (a, b) in creases {
+            hasher.write_usize(a);
+            hasher.write_usize(b);
+        }
+    }
+
+    /// Loads the mesh cached at `path` for `key`, or gets it from `process` and saves it there.
+    /// `key` identifies the input of the processing, typically a `CacheHasher` fed with
+    /// the geometry of the source mesh and the processing parameters. Failing to save the cache
+    /// is only reported, the processed mesh being returned anyway.
+    pub fn cached(path: &str, key: u64, process: impl FnOnce() -> Self) -> Result<Self, Error> {
+        match Self::load_cache(path, key) {
+            Ok(mesh) => Ok(mesh),
+            Err(_) => {
+                let mesh = process();
+                if let Err(error) = mesh.save_cache(path, key) {
+                    eprintln!("Could not save the mesh cache {}: {}", path, error);
+                }
+                Ok(mesh)
+            },
+        }
+    }
+
+    fn sorted_creases(&self) -> Vec<(usize, usize)> {
+        let mut creases: Vec<(usize, usize)> = self.creases.iter().copied().collect();
+        creases.sort_unstable();
+        creases
+    }
+
+    fn save_cache(&self, path: &str, key: u64) -> Result<(), Error> {
+        let mut writer = CacheWriter::new(KIND, key);
+
+        writer.write_usize(self.positions.len());
+        for position in &self.positions {
+            writer.write_f64(position.x());
+            writer.write_f64(position.y());
+            writer.write_f64(position.z());
+        }
+
+        writer.write_usize(self.faces.len());
+        for face in &self.faces {
+            writer.write_usize(face.len());
+            face.iter().for_each(|&vertex| writer.write_u32(vertex as u32));
+        }
+
+        // The texture coordinates follow the faces, their count is the one of the face
+        writer.write_u8(self.uvs.is_some() as u8);
+        for uv in self.uvs.iter().flatten().flatten() {
+            writer.write_f64(uv.u);
+            writer.write_f64(uv.v);
+        }
+
+        let creases = self.sorted_creases();
+        writer.write_usize(creases.len());
+        for (a, b) in creases {
+            writer.write_u32(a as u32);
+            writer.write_u32(b as u32);
+        }
+
+        writer.save(path)
+    }
+
+    /// Reads the mesh back, checking all its indices are valid
+    fn load_cache(path: &str, key: u64) -> Result<Self, Error> {
+        let mut reader = CacheReader::open(path, KIND, key)?;
+
+        let position_count = reader.read_len(3 * 8)?;
+        let mut positions = Vec::with_capacity(position_count);
+        for _ in 0..position_count {
+            positions.push(Point::new(reader.read_f64()?, reader.read_f64()?, reader.read_f64()?));
+        }
+
+        let face_count = reader.read_len(8)?;
+        let mut faces = Vec::with_capacity(face_count);
+        for _ in 0..face_count {
+            let len = reader.read_len(4)?;
+            let mut face = Vec::with_capacity(len);
+            for _ in 0..len {
+                let vertex = reader.read_u32()? as usize;
+                if vertex >= positions.len() {
+                    return Err(reader.invalid("face vertex out of range"));
+                }
+                face.push(vertex);
+            }
+            faces.push(face);
+        }
+
+        let uvs = match reader.read_u8()? {
+            0 => None,
+            1 => {
+                let mut uvs = Vec::with_capacity(faces.len());
+                for face in &faces {
+                    let mut face_uvs = Vec::with_capacity(face.len());
+                    for _ in 0..face.len() {
+                        face_uvs.push(Uv { u: reader.read_f64()?, v: reader.read_f64()? });
+                    }
+                    uvs.push(face_uvs);
+                }
+                Some(uvs)
+            },
+            _ => return Err(reader.invalid("invalid texture coordinates flag")),
+        };
+
+        let mut mesh = Self::new(positions, faces);
+        mesh.uvs = uvs;
+        let crease_count = reader.read_len(2 * 4)?;
+        for _ in 0..crease_count {
+            let (a, b) = (reader.read_u32()? as usize, reader.read_u32()? as usize);
+            if a >= mesh.positions.len() || b >= mesh.positions.len() {
+                return Err(reader.invalid("crease vertex out of range"));
+            }
+            mesh.add_crease(a, b);
+        }
+        reader.finish()?;
+
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_cached_mesh() {
+        let directory = TempDir::new("mesh_cache").unwrap();
+        let path = directory.path().join("quad.mesh");
+        let path = path.to_str().unwrap();
+
+        let mut quad = PolygonMesh::parse_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\nf 1/1 2/2 3/3 4/4\n").unwrap();
+        quad.add_crease(0, 1);
+        let mut hasher = CacheHasher::new();
+        quad.hash_geometry(&mut hasher);
+        let key = hasher.finish();
+
+        let processed = PolygonMesh::cached(path, key, || quad.catmull_clark(2)).unwrap();
+        let loaded = PolygonMesh::cached(path, key, || panic!("The mesh should come from the cache")).unwrap();
+        assert_eq!(processed.positions, loaded.positions);
+        assert_eq!(processed.faces, loaded.faces);
+        assert_eq!(processed.sorted_creases(), loaded.sorted_creases());
+        let uv = |mesh: &PolygonMesh| mesh.uvs.as_ref().unwrap().iter().flatten().map(|uv| (uv.u, uv.v)).collect::<Vec<_>>();
+        assert_eq!(uv(&processed), uv(&loaded));
+
+        // Another key processes the mesh again
+        let other = PolygonMesh::cached(path, key + 1, || quad.clone()).unwrap();
+        assert_eq!(other.faces.len(), 1);
+    }
+
+    #[test]
+    fn test_unwritable_cache() {
+        let directory = TempDir::new("mesh_cache").unwrap();
+        // The directory can't be created, a file being there
+        std::fs::write(directory.path().join("file"), "").unwrap();
+        let path = directory.path().join("file").join("quad.mesh");
+
+        let quad = PolygonMesh::parse_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n").unwrap();
+        let processed = PolygonMesh::cached(path.to_str().unwrap(), 0, || quad.catmull_clark(1)).unwrap();
+        assert_eq!(processed.faces.len(), 4);
+        assert!(!path.exists());
+    }
+}
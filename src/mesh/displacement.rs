@@ -0,0 +1,52 @@
+use crate::mesh::PolygonMesh;
+use crate::texture::{Texture, Uv};
+
+impl PolygonMesh {
+    /// Moves every vertex along its normal by the texture scalar value times `scale`.
+    /// The texture is looked up with the first uv coordinates found for the vertex, so shared vertices
+    /// stay shared and the surface has no cracks. Meant to be applied once the mesh has been subdivided
+    /// enough to carry the details of the texture.
+    pub fn displaced(&self, texture: &dyn Texture, scale: f64) -> Self {
+        let mut vertex_uvs: Vec<Option<Uv>> = vec![None; self.positions.len()];
+        if let Some(uvs) = &self.uvs {
+            for (face, face_uvs) in self.faces.iter().zip(uvs) {
+                for (&vertex, &uv) in face.iter().zip(face_uvs) {
+                    vertex_uvs[vertex].get_or_insert(uv);
+                }
+            }
+        }
+
+        let positions = self.positions.iter()
+            .zip(self.vertex_normals())
+            .zip(vertex_uvs)
+            .map(|((&position, normal), uv)| {
+                let uv = uv.unwrap_or(Uv { u: 0., v: 0. });
+                position + scale * texture.scalar(&uv, &position) * normal
+            })
+            .collect();
+
+        Self { positions, ..self.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::{Point, Vec3};
+    use crate::texture::CheckerTexture;
+    use crate::texture::SolidColor;
+
+    #[test]
+    fn test_displaced_grid() {
+        // Checker of heights 0 and 1 over a flat grid lying in the xz plane, facing down
+        let texture = CheckerTexture::new(SolidColor::from_rgb(0., 0., 0.), SolidColor::from_rgb(1., 1., 1.), 1.);
+        let mesh = PolygonMesh::grid(Point::new(0.25, 0., 0.25), Vec3::new(1., 0., 0.), Vec3::new(0., 0., 1.), 2, 2);
+        let displaced = mesh.displaced(&*texture, 0.5);
+
+        for (before, after) in mesh.positions.iter().zip(&displaced.positions) {
+            let value = texture.value(&Uv { u: 0., v: 0. }, before);
+            assert!((*after - (*before - Vec3::new(0., 0.5 * value.r, 0.))).length() < 1e-9);
+        }
+        assert_eq!(displaced.faces, mesh.faces);
+    }
+}
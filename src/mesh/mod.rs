@@ -0,0 +1,101 @@
+use crate::vector::{Point, Vec3};
+use crate::texture::Uv;
+
+use std::collections::HashSet;
+
+mod obj;
+mod subdivision;
+mod tessellation;
+mod displacement;
+mod cache;
+
+/// Polygon mesh: shared vertex positions, faces listing their vertices counter-clockwise,
+/// optional texture coordinates per face corner, and the sharp edges kept by the subdivision.
+#[derive(Clone, Debug, Default)]
+pub struct PolygonMesh {
+    pub positions: Vec<Point>,
+    pub faces: Vec<Vec<usize>>,
+    pub uvs: Option<Vec<Vec<Uv>>>,
+    creases: HashSet<(usize, usize)>,
+}
+
+impl PolygonMesh {
+    pub fn new(positions: Vec<Point>, faces: Vec<Vec<usize>>) -> Self {
+        Self {
+            positions,
+            faces,
+            uvs: None,
+            creases: HashSet::new(),
+        }
+    }
+
+    pub fn with_uvs(mut self, uvs: Vec<Vec<Uv>>) -> Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    /// Uniformly scales the mesh around the origin, then moves it by `offset`
+    pub fn transformed(mut self, scale: f64, offset: Vec3) -> Self {
+        for position in self.positions.iter_mut() {
+            *position = scale * *position + offset;
+        }
+        self
+    }
+
+    fn edge(a: usize, b: usize) -> (usize, usize) {
+        (a.min(b), a.max(b))
+    }
+
+    /// Marks the edge between the two vertices as sharp
+    pub fn add_crease(&mut self, a: usize, b: usize) {
+        self.creases.insert(Self::edge(a, b));
+    }
+
+    pub fn is_crease(&self, a: usize, b: usize) -> bool {
+        self.creases.contains(&Self::edge(a, b))
+    }
+
+    pub fn is_triangle_mesh(&self) -> bool {
+        self.faces.iter().all(|face| face.len() == 3)
+    }
+
+    /// Splits every polygon into a fan of triangles
+    pub fn triangulate(&self) -> Self {
+        let mut faces = Vec::new();
+        let mut uvs = Vec::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            for i in 1..face.len().saturating_sub(1) {
+                faces.push(vec![face[0], face[i], face[i + 1]]);
+                if let Some(face_uvs) = &self.uvs {
+                    uvs.push(vec![face_uvs[f][0], face_uvs[f][i], face_uvs[f][i + 1]]);
+                }
+            }
+        }
+
+        Self {
+            positions: self.positions.clone(),
+            faces,
+            uvs: self.uvs.as_ref().map(|_| uvs),
+            creases: self.creases.clone(),
+        }
+    }
+
+    /// Area weighted normals of the vertices, for smooth shading
+    pub fn vertex_normals(&self) -> Vec<Vec3> {
+        let mut normals = vec![Vec3::zero(); self.positions.len()];
+        for face in &self.faces {
+            let origin = self.positions[face[0]];
+            let mut face_normal = Vec3::zero();
+            for i in 1..face.len().saturating_sub(1) {
+                face_normal += (self.positions[face[i]] - origin).cross(&(self.positions[face[i + 1]] - origin));
+            }
+            for &vertex in face {
+                normals[vertex] += face_normal;
+            }
+        }
+
+        normals.into_iter()
+            .map(|n| if n.near_zero() { Vec3::new(0., 1., 0.) } else { n.normalized() })
+            .collect()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::vector::Point;
+use crate::mesh::PolygonMesh;
+use crate::texture::Uv;
+
+use std::fs;
+use std::io::{Error, ErrorKind};
+
+impl PolygonMesh {
+    /// Loads the vertices, texture coordinates and faces of a Wavefront OBJ file (other statements are ignored)
+    pub fn load_obj(path: &str) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+        Self::parse_obj(&content).map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}: {}", path, e)))
+    }
+
+    pub fn parse_obj(content: &str) -> Result<Self, String> {
+        let mut positions = Vec::new();
+        let mut texture_coordinates = Vec::new();
+        let mut faces = Vec::new();
+        let mut face_uvs = Vec::new();
+        let mut all_faces_have_uvs = true;
+
+        for (line_number, line) in content.lines().enumerate() {
+            let error = |message: &str| format!("line {}: {}", line_number + 1, message);
+            let mut tokens = line.split_whitespace();
+
+            match tokens.next() {
+                Some("v") => {
+                    let coordinates = Self::parse_floats(tokens, 3).ok_or_else(|| error("invalid vertex"))?;
+                    positions.push(Point::new(coordinates[0], coordinates[1], coordinates[2]));
+                },
+                Some("vt") => {
+                    let coordinates = Self::parse_floats(tokens, 2).ok_or_else(|| error("invalid texture coordinates"))?;
+                    texture_coordinates.push(Uv { u: coordinates[0], v: coordinates[1] });
+                },
+                Some("f") => {
+                    let mut face = Vec::new();
+                    let mut uvs = Vec::new();
+                    for corner in tokens {
+                        // v, v/vt, v//vn or v/vt/vn, indices starting at 1 (or negative, relative to the end)
+                        let mut indices = corner.split('/');
+                        let vertex = Self::resolve_index(indices.next(), positions.len()).ok_or_else(|| error("invalid face vertex"))?;
+                        face.push(vertex);
+
+                        match Self::resolve_index(indices.next(), texture_coordinates.len()) {
+                            Some(uv) => uvs.push(texture_coordinates[uv]),
+                            None => all_faces_have_uvs = false,
+                        }
+                    }
+
+                    if face.len() < 3 {
+                        return Err(error("face with less than 3 vertices"));
+                    }
+                    faces.push(face);
+                    face_uvs.push(uvs);
+                },
+                _ => {},
+            }
+        }
+
+        let mesh = Self::new(positions, faces);
+        Ok(if all_faces_have_uvs && !mesh.faces.is_empty() { mesh.with_uvs(face_uvs) } else { mesh })
+    }
+
+    fn parse_floats<'a>(tokens: impl Iterator<Item = &'a str>, count: usize) -> Option<Vec<f64>> {
+        let values = tokens.take(count).map(|t| t.parse::<f64>().ok()).collect::<Option<Vec<f64>>>()?;
+        if values.len() == count { Some(values) } else { None }
+    }
+
+    fn resolve_index(token: Option<&str>, count: usize) -> Option<usize> {
+        let index = token?.parse::<i64>().ok()?;
+        let resolved = if index < 0 { count as i64 + index } else { index - 1 };
+        if resolved >= 0 && (resolved as usize) < count { Some(resolved as usize) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_obj() {
+        let content = "# Square\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\nvn 0 0 1\nf 1/1/1 2/2/1 3/3/1 4/4/1\n";
+        let mesh = PolygonMesh::parse_obj(content).unwrap();
+        assert_eq!(mesh.positions.len(), 4);
+        assert_eq!(mesh.faces, vec![vec![0, 1, 2, 3]]);
+        assert_eq!(mesh.uvs.as_ref().unwrap()[0][2].u, 1.);
+
+        let triangles = mesh.triangulate();
+        assert_eq!(triangles.faces, vec![vec![0, 1, 2], vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn test_parse_obj_errors() {
+        assert!(PolygonMesh::parse_obj("v 0 0 0\nv 1 0 0\nf 1 2 3\n").is_err());
+        assert!(PolygonMesh::parse_obj("v 0 0\n").is_err());
+
+        let mesh = PolygonMesh::parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\n").unwrap();
+        assert_eq!(mesh.faces, vec![vec![0, 1, 2]]);
+        assert!(mesh.uvs.is_none());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::vector::Point;
+use crate::mesh::PolygonMesh;
+use crate::texture::Uv;
+
+use std::collections::{HashMap, HashSet};
+
+fn uv_lerp(a: &Uv, b: &Uv, t: f64) -> Uv {
+    Uv { u: a.u + (b.u - a.u) * t, v: a.v + (b.v - a.v) * t }
+}
+
+fn uv_average(uvs: &[Uv]) -> Uv {
+    let n = uvs.len() as f64;
+    Uv {
+        u: uvs.iter().map(|uv| uv.u).sum::<f64>() / n,
+        v: uvs.iter().map(|uv| uv.v).sum::<f64>() / n,
+    }
+}
+
+/// Connectivity of a polygon mesh, with the edges listed in a deterministic order
+struct Topology {
+    edges: Vec<(usize, usize)>,
+    edge_index: HashMap<(usize, usize), usize>,
+    edge_faces: Vec<Vec<usize>>,
+    vertex_neighbors: Vec<Vec<usize>>,
+    vertex_faces: Vec<Vec<usize>>,
+}
+
+impl Topology {
+    fn new(mesh: &PolygonMesh) -> Self {
+        let mut edges = Vec::new();
+        let mut edge_index = HashMap::new();
+        let mut edge_faces: Vec<Vec<usize>> = Vec::new();
+        let mut vertex_neighbors = vec![Vec::new(); mesh.positions.len()];
+        let mut vertex_faces = vec![Vec::new(); mesh.positions.len()];
+
+        for (f, face) in mesh.faces.iter().enumerate() {
+            for (i, &a) in face.iter().enumerate() {
+                vertex_faces[a].push(f);
+
+                let b = face[(i + 1) % face.len()];
+                let edge = PolygonMesh::edge(a, b);
+                let index = *edge_index.entry(edge).or_insert_with(|| {
+                    edges.push(edge);
+                    edge_faces.push(Vec::new());
+                    vertex_neighbors[a].push(b);
+                    vertex_neighbors[b].push(a);
+                    edges.len() - 1
+                });
+                edge_faces[index].push(f);
+            }
+        }
+
+        Self { edges, edge_index, edge_faces, vertex_neighbors, vertex_faces }
+    }
+
+    /// Boundary edges are always sharp, as well as the creases
+    fn is_sharp(&self, mesh: &PolygonMesh, edge: usize) -> bool {
+        self.edge_faces[edge].len() != 2 || mesh.creases.contains(&self.edges[edge])
+    }
+
+    fn sharp_neighbors(&self, mesh: &PolygonMesh, vertex: usize) -> Vec<usize> {
+        self.vertex_neighbors[vertex].iter()
+            .cloned()
+            .filter(|&n| self.is_sharp(mesh, self.edge_index[&PolygonMesh::edge(vertex, n)]))
+            .collect()
+    }
+
+    fn subdivided_creases(&self, mesh: &PolygonMesh, edge_points: &[usize]) -> HashSet<(usize, usize)> {
+        let mut creases = HashSet::new();
+        for &(a, b) in &mesh.creases {
+            if let Some(&edge) = self.edge_index.get(&(a, b)) {
+                creases.insert(PolygonMesh::edge(a, edge_points[edge]));
+                creases.insert(PolygonMesh::edge(edge_points[edge], b));
+            }
+        }
+        creases
+    }
+}
+
+impl PolygonMesh {
+    /// Loop subdivision, for triangle meshes (other polygons are triangulated first)
+    pub fn loop_subdivide(&self, levels: usize) -> Self {
+        let mut mesh = if self.is_triangle_mesh() { self.clone() } else { self.triangulate() };
+        for _ in 0..levels {
+            mesh = mesh.loop_step();
+        }
+        mesh
+    }
+
+    /// Catmull-Clark subdivision, for any polygon mesh (the result is made of quads only)
+    pub fn catmull_clark(&self, levels: usize) -> Self {
+        let mut mesh = self.clone();
+        for _ in 0..levels {
+            mesh = mesh.catmull_clark_step();
+        }
+        mesh
+    }
+
+    fn loop_step(&self) -> Self {
+        let topology = Topology::new(self);
+
+        // Move the existing vertices
+        let mut positions: Vec<Point> = (0..self.positions.len()).map(|v| {
+            let position = self.positions[v];
+            let neighbors = &topology.vertex_neighbors[v];
+            let sharp_neighbors = topology.sharp_neighbors(self, v);
+
+            match sharp_neighbors.len() {
+                0 | 1 if !neighbors.is_empty() => {
+                    let n = neighbors.len() as f64;
+                    let beta = if neighbors.len() == 3 { 3. / 16. } else { 3. / (8. * n) };
+                    let sum = neighbors.iter().fold(Point::zero(), |acc, &i| acc + self.positions[i]);
+                    (1. - n * beta) * position + beta * sum
+                },
+                2 => 0.75 * position + 0.125 * (self.positions[sharp_neighbors[0]] + self.positions[sharp_neighbors[1]]),
+                _ => position,
+            }
+        }).collect();
+
+        // Add a vertex on each edge
+        let mut edge_points = Vec::with_capacity(topology.edges.len());
+        for (e, &(a, b)) in topology.edges.iter().enumerate() {
+            let ends = self.positions[a] + self.positions[b];
+            let point = if topology.is_sharp(self, e) {
+                ends / 2.
+            } else {
+                let opposite = topology.edge_faces[e].iter().fold(Point::zero(), |acc, &f| {
+                    let vertex = self.faces[f].iter().find(|&&v| v != a && v != b).unwrap();
+                    acc + self.positions[*vertex]
+                });
+                0.375 * ends + 0.125 * opposite
+            };
+            edge_points.push(positions.len());
+            positions.push(point);
+        }
+
+        // Split each triangle in 4
+        let mut faces = Vec::with_capacity(self.faces.len() * 4);
+        let mut uvs = Vec::with_capacity(self.faces.len() * 4);
+        for (f, face) in self.faces.iter().enumerate() {
+            let [a, b, c] = [face[0], face[1], face[2]];
+            let edge_point = |x: usize, y: usize| edge_points[topology.edge_index[&Self::edge(x, y)]];
+            let (ab, bc, ca) = (edge_point(a, b), edge_point(b, c), edge_point(c, a));
+
+            faces.push(vec![a, ab, ca]);
+            faces.push(vec![ab, b, bc]);
+            faces.push(vec![ca, bc, c]);
+            faces.push(vec![ab, bc, ca]);
+
+            if let Some(face_uvs) = &self.uvs {
+                let [ua, ub, uc] = [face_uvs[f][0], face_uvs[f][1], face_uvs[f][2]];
+                let (uab, ubc, uca) = (uv_lerp(&ua, &ub, 0.5), uv_lerp(&ub, &uc, 0.5), uv_lerp(&uc, &ua, 0.5));
+                uvs.push(vec![ua, uab, uca]);
+                uvs.push(vec![uab, ub, ubc]);
+                uvs.push(vec![uca, ubc, uc]);
+                uvs.push(vec![uab, ubc, uca]);
+            }
+        }
+
+        Self {
+            positions,
+            faces,
+            uvs: self.uvs.as_ref().map(|_| uvs),
+            creases: topology.subdivided_creases(self, &edge_points),
+        }
+    }
+
+    fn catmull_clark_step(&self) -> Self {
+        let topology = Topology::new(self);
+
+        let face_points: Vec<Point> = self.faces.iter()
+            .map(|face| face.iter().fold(Point::zero(), |acc, &v| acc + self.positions[v]) / face.len() as f64)
+            .collect();
+
+        // Move the existing vertices
+        let mut positions: Vec<Point> = (0..self.positions.len()).map(|v| {
+            let position = self.positions[v];
+            let neighbors = &topology.vertex_neighbors[v];
+            let sharp_neighbors = topology.sharp_neighbors(self, v);
+
+            match sharp_neighbors.len() {
+                0 | 1 if !neighbors.is_empty() => {
+                    let n = neighbors.len() as f64;
+                    let faces = &topology.vertex_faces[v];
+                    let face_average = faces.iter().fold(Point::zero(), |acc, &f| acc + face_points[f]) / faces.len() as f64;
+                    let edge_average = neighbors.iter().fold(Point::zero(), |acc, &i| acc + (position + self.positions[i]) / 2.) / n;
+                    (face_average + 2. * edge_average + (n - 3.) * position) / n
+                },
+                2 => (self.positions[sharp_neighbors[0]] + 6. * position + self.positions[sharp_neighbors[1]]) / 8.,
+                _ => position,
+            }
+        }).collect();
+
+        // Add a vertex on each edge
+        let mut edge_points = Vec::with_capacity(topology.edges.len());
+        for (e, &(a, b)) in topology.edges.iter().enumerate() {
+            let ends = self.positions[a] + self.positions[b];
+            let point = if topology.is_sharp(self, e) {
+                ends / 2.
+            } else {
+                let [f1, f2] = [topology.edge_faces[e][0], topology.edge_faces[e][1]];
+                (ends + face_points[f1] + face_points[f2]) / 4.
+            };
+            edge_points.push(positions.len());
+            positions.push(point);
+        }
+
+        // Split each face in quads around its center
+        let mut faces = Vec::new();
+        let mut uvs = Vec::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            let center = positions.len();
+            positions.push(face_points[f]);
+
+            let n = face.len();
+            let edge_point = |x: usize, y: usize| edge_points[topology.edge_index[&Self::edge(x, y)]];
+            for i in 0..n {
+                let (previous, current, next) = (face[(i + n - 1) % n], face[i], face[(i + 1) % n]);
+                faces.push(vec![current, edge_point(current, next), center, edge_point(previous, current)]);
+            }
+
+            if let Some(face_uvs) = &self.uvs {
+                let corners = &face_uvs[f];
+                let center_uv = uv_average(corners);
+                for i in 0..n {
+                    let (previous, current, next) = (corners[(i + n - 1) % n], corners[i], corners[(i + 1) % n]);
+                    uvs.push(vec![current, uv_lerp(&current, &next, 0.5), center_uv, uv_lerp(&previous, &current, 0.5)]);
+                }
+            }
+        }
+
+        Self {
+            positions,
+            faces,
+            uvs: self.uvs.as_ref().map(|_| uvs),
+            creases: topology.subdivided_creases(self, &edge_points),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::Vec3;
+
+    fn cube() -> PolygonMesh {
+        let positions = vec![
+            Point::new(-1., -1., -1.), Point::new(1., -1., -1.), Point::new(1., 1., -1.), Point::new(-1., 1., -1.),
+            Point::new(-1., -1., 1.), Point::new(1., -1., 1.), Point::new(1., 1., 1.), Point::new(-1., 1., 1.),
+        ];
+        let faces = vec![
+            vec![0, 3, 2, 1], vec![4, 5, 6, 7], vec![0, 1, 5, 4],
+            vec![2, 3, 7, 6], vec![1, 2, 6, 5], vec![0, 4, 7, 3],
+        ];
+        PolygonMesh::new(positions, faces)
+    }
+
+    #[test]
+    fn test_catmull_clark_counts() {
+        let mesh = cube().catmull_clark(1);
+        assert_eq!(mesh.positions.len(), 8 + 12 + 6);
+        assert_eq!(mesh.faces.len(), 24);
+        assert!(mesh.faces.iter().all(|face| face.len() == 4));
+
+        // The cube shrinks towards a sphere
+        let corner = mesh.positions[6];
+        assert!(corner.length() < Vec3::new(1., 1., 1.).length());
+        assert!((corner.x() - 5. / 9.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_catmull_clark_creases() {
+        // A crease all around the top face keeps it flat
+        let mut mesh = cube();
+        for (a, b) in [(4, 5), (5, 6), (6, 7), (7, 4)] {
+            mesh.add_crease(a, b);
+        }
+        let mesh = mesh.catmull_clark(2);
+        let top: Vec<&Point> = mesh.positions.iter().filter(|p| p.z() > 0.999).collect();
+        assert!(top.len() > 4);
+        assert!(top.iter().all(|p| (p.z() - 1.).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_loop_counts() {
+        let mesh = cube().loop_subdivide(2);
+        assert_eq!(mesh.faces.len(), 12 * 16);
+        assert!(mesh.is_triangle_mesh());
+    }
+
+    #[test]
+    fn test_loop_boundary() {
+        // A flat open square stays flat, its boundary being sharp
+        let positions = vec![Point::new(0., 0., 0.), Point::new(1., 0., 0.), Point::new(1., 1., 0.), Point::new(0., 1., 0.)];
+        let mesh = PolygonMesh::new(positions, vec![vec![0, 1, 2], vec![0, 2, 3]]).loop_subdivide(3);
+        assert!(mesh.positions.iter().all(|p| p.z() == 0.));
+    }
+}
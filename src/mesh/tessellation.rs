@@ -0,0 +1,95 @@
+use crate::vector::{Point, Vec3};
+use crate::mesh::PolygonMesh;
+use crate::texture::Uv;
+
+use std::f64::consts::PI;
+
+impl PolygonMesh {
+    /// Parallelogram from the corner Q spanned by u and v, split in a grid of quads.
+    /// The uv coordinates and the orientation match the ones of `Quad`.
+    pub fn grid(q: Point, u: Vec3, v: Vec3, nu: usize, nv: usize) -> Self {
+        let (nu, nv) = (nu.max(1), nv.max(1));
+        let index = |i: usize, j: usize| j * (nu + 1) + i;
+        let uv = |i: usize, j: usize| Uv { u: i as f64 / nu as f64, v: j as f64 / nv as f64 };
+
+        let mut positions = Vec::with_capacity((nu + 1) * (nv + 1));
+        for j in 0..=nv {
+            for i in 0..=nu {
+                let Uv { u: alpha, v: beta } = uv(i, j);
+                positions.push(q + alpha * u + beta * v);
+            }
+        }
+
+        let mut faces = Vec::with_capacity(nu * nv);
+        let mut uvs = Vec::with_capacity(nu * nv);
+        for j in 0..nv {
+            for i in 0..nu {
+                faces.push(vec![index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]);
+                uvs.push(vec![uv(i, j), uv(i + 1, j), uv(i + 1, j + 1), uv(i, j + 1)]);
+            }
+        }
+
+        Self::new(positions, faces).with_uvs(uvs)
+    }
+
+    /// Sphere made of `segments` slices around the y axis and `rings` stacks from pole to pole,
+    /// quads everywhere but triangles around the poles. The uv coordinates match the ones of `Sphere`.
+    pub fn sphere(center: Point, radius: f64, segments: usize, rings: usize) -> Self {
+        let (segments, rings) = (segments.max(3), rings.max(2));
+        let uv = |i: usize, j: usize| Uv { u: i as f64 / segments as f64, v: j as f64 / rings as f64 };
+
+        // Poles first, then the inner rings
+        let mut positions = vec![center - Vec3::new(0., radius, 0.), center + Vec3::new(0., radius, 0.)];
+        for j in 1..rings {
+            for i in 0..segments {
+                let Uv { u, v } = uv(i, j);
+                let (theta, phi) = (v * PI, u * 2. * PI);
+                positions.push(center + radius * Vec3::new(-phi.cos() * theta.sin(), -theta.cos(), phi.sin() * theta.sin()));
+            }
+        }
+        let index = |i: usize, j: usize| match j {
+            0 => 0,
+            j if j == rings => 1,
+            j => 2 + (j - 1) * segments + i % segments,
+        };
+
+        let mut faces = Vec::with_capacity(segments * rings);
+        let mut uvs = Vec::with_capacity(segments * rings);
+        for j in 0..rings {
+            for i in 0..segments {
+                let pole_uv = |j: usize| Uv { u: (i as f64 + 0.5) / segments as f64, v: j as f64 / rings as f64 };
+                if j == 0 {
+                    faces.push(vec![index(i, 0), index(i + 1, 1), index(i, 1)]);
+                    uvs.push(vec![pole_uv(0), uv(i + 1, 1), uv(i, 1)]);
+                } else if j == rings - 1 {
+                    faces.push(vec![index(i, j), index(i + 1, j), index(i, rings)]);
+                    uvs.push(vec![uv(i, j), uv(i + 1, j), pole_uv(rings)]);
+                } else {
+                    faces.push(vec![index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1)]);
+                    uvs.push(vec![uv(i, j), uv(i + 1, j), uv(i + 1, j + 1), uv(i, j + 1)]);
+                }
+            }
+        }
+
+        Self::new(positions, faces).with_uvs(uvs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_orientation() {
+        let center = Point::new(1., 2., 3.);
+        let mesh = PolygonMesh::sphere(center, 2., 12, 6);
+        assert_eq!(mesh.positions.len(), 2 + 5 * 12);
+        assert_eq!(mesh.faces.len(), 12 * 6);
+
+        // Every vertex lies on the sphere, and the faces are oriented outwards
+        assert!(mesh.positions.iter().all(|&p| ((p - center).length() - 2.).abs() < 1e-9));
+        for (position, normal) in mesh.positions.iter().zip(mesh.vertex_normals()) {
+            assert!((*position - center).dot(&normal) > 0.);
+        }
+    }
+}
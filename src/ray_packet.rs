@@ -0,0 +1,189 @@
+use crate::ray::Ray;
+use crate::interval::Interval;
+use crate::hittable::AABB;
+
+/// Packets hold up to 16 rays, so a `u16` mask can tell which of them are still traced
+pub const MAX_PACKET_SIZE: usize = 16;
+
+/// Rays traced together through the BVH, like the primary rays of a tile of pixels.
+/// Coherent packets, whose directions have the same signs on every axis, get a frustum
+/// bounding all their rays, culling whole boxes with a single test. Incoherent ones,
+/// typically after diffuse bounces, have none and fall back to single rays.
+#[derive(Clone)]
+pub struct RayPacket {
+    rays: [Ray; MAX_PACKET_SIZE],
+    len: usize,
+    frustum: Option<Frustum>,
+}
+
+/// Bounds of the origins and inverse directions of the rays of a coherent packet
+#[derive(Clone, Copy)]
+struct Frustum {
+    origin_min: [f64; 3],
+    origin_max: [f64; 3],
+    inv_direction_min: [f64; 3],
+    inv_direction_max: [f64; 3],
+    direction_is_negative: [bool; 3],
+}
+
+impl RayPacket {
+    pub fn new(rays: &[Ray]) -> Self {
+        if rays.is_empty() || rays.len() > MAX_PACKET_SIZE {
+            panic!("Ray packets hold between 1 and {} rays, not {}", MAX_PACKET_SIZE, rays.len());
+        }
+
+        let mut packet_rays = [rays[0]; MAX_PACKET_SIZE];
+        packet_rays[..rays.len()].copy_from_slice(rays);
+
+        Self {
+            rays: packet_rays,
+            len: rays.len(),
+            frustum: Frustum::new(rays),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn rays(&self) -> &[Ray] {
+        &self.rays[..self.len]
+    }
+
+    /// Mask of all the rays of the packet
+    pub fn all(&self) -> u16 {
+        ((1u32 << self.len) - 1) as u16
+    }
+
+    /// Rays of the mask, with their index in the packet
+    pub fn active(&self, mask: u16) -> impl Iterator<Item = (usize, &Ray)> {
+        self.rays().iter().enumerate().filter(move |(i, _)| mask & (1 << i) != 0)
+    }
+
+    pub fn is_coherent(&self) -> bool {
+        self.frustum.is_some()
+    }
+
+    /// Sign of the directions of a coherent packet along an axis
+    pub fn direction_is_negative(&self, axis: usize) -> bool {
+        self.frustum.is_some_and(|frustum| frustum.direction_is_negative[axis])
+    }
+
+    /// Conservative test of the box against the frustum: false only if none of the rays can hit it
+    /// during `ray_t`. Always true for incoherent packets.
+    pub fn may_hit(&self, bbox: &AABB, ray_t: &Interval) -> bool {
+        match &self.frustum {
+            Some(frustum) => frustum.may_hit(bbox, ray_t),
+            None => true,
+        }
+    }
+}
+
+impl Frustum {
+    fn new(rays: &[Ray]) -> Option<Self> {
+        let mut frustum = Self {
+            origin_min: [f64::INFINITY; 3],
+            origin_max: [-f64::INFINITY; 3],
+            inv_direction_min: [f64::INFINITY; 3],
+            inv_direction_max: [-f64::INFINITY; 3],
+            direction_is_negative: [rays[0].direction().x() < 0., rays[0].direction().y() < 0., rays[0].direction().z() < 0.],
+        };
+
+        for ray in rays {
+            for axis in 0..3 {
+                let direction = ray.direction().axis(axis);
+                let inv_direction = ray.inv_direction().axis(axis);
+                if direction == 0. || !inv_direction.is_finite() || (direction < 0.) != frustum.direction_is_negative[axis] {
+                    return None;
+                }
+
+                frustum.origin_min[axis] = frustum.origin_min[axis].min(ray.origin().axis(axis));
+                frustum.origin_max[axis] = frustum.origin_max[axis].max(ray.origin().axis(axis));
+                frustum.inv_direction_min[axis] = frustum.inv_direction_min[axis].min(inv_direction);
+                frustum.inv_direction_max[axis] = frustum.inv_direction_max[axis].max(inv_direction);
+            }
+        }
+
+        Some(frustum)
+    }
+
+    /// The slab test of `AABB::hit_interval` in interval arithmetic. Float subtractions and products being
+    /// monotonic, the bounds computed here contain the exact values every ray of the packet gets.
+    fn may_hit(&self, bbox: &AABB, ray_t: &Interval) -> bool {
+        let mut tmin = ray_t.min;
+        let mut tmax = ray_t.max;
+
+        for axis in 0..3 {
+            let (t1_min, t1_max) = self.plane_distances(bbox.axis(axis).min, axis);
+            let (t2_min, t2_max) = self.plane_distances(bbox.axis(axis).max, axis);
+
+            tmin = tmin.max(t1_min.min(t2_min));
+            tmax = tmax.min(t1_max.max(t2_max));
+        }
+
+        tmin <= tmax
+    }
+
+    /// Bounds of the distances at which the rays cross the plane at `position` along the axis
+    fn plane_distances(&self, position: f64, axis: usize) -> (f64, f64) {
+        let offsets = [position - self.origin_max[axis], position - self.origin_min[axis]];
+        let inv_directions = [self.inv_direction_min[axis], self.inv_direction_max[axis]];
+
+        let mut bounds = (f64::INFINITY, -f64::INFINITY);
+        for offset in offsets {
+            for inv_direction in inv_directions {
+                let t = offset * inv_direction;
+                bounds = (bounds.0.min(t), bounds.1.max(t));
+            }
+        }
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::{Point, Vec3};
+
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_coherence() {
+        let origin = Point::new(0., 0., 0.);
+        let coherent = RayPacket::new(&[Ray::new(origin, Vec3::new(0.1, 0.2, -1.), 0.), Ray::new(origin, Vec3::new(0.3, 0.1, -1.), 0.)]);
+        assert!(coherent.is_coherent());
+        assert!(coherent.direction_is_negative(2));
+        assert_eq!(coherent.all(), 0b11);
+
+        let incoherent = RayPacket::new(&[Ray::new(origin, Vec3::new(0.1, 0.2, -1.), 0.), Ray::new(origin, Vec3::new(-0.3, 0.1, -1.), 0.)]);
+        assert!(!incoherent.is_coherent());
+    }
+
+    #[test]
+    fn test_frustum_is_conservative() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            // Rays with diverging origins and directions, like a defocused camera or shadow rays
+            let rays: Vec<Ray> = (0..MAX_PACKET_SIZE).map(|_| {
+                let origin = Point::new(rng.gen_range(-1.0..1.), rng.gen_range(-1.0..1.), 5.);
+                let direction = Vec3::new(rng.gen_range(0.01..0.5), rng.gen_range(-0.5..-0.01), -1.);
+                Ray::new(origin, direction, 0.)
+            }).collect();
+            let packet = RayPacket::new(&rays);
+
+            let corner = Point::new(rng.gen_range(-5.0..5.), rng.gen_range(-5.0..5.), rng.gen_range(-5.0..5.));
+            let size = Vec3::new(rng.gen_range(0.0..2.), rng.gen_range(0.0..2.), rng.gen_range(0.0..2.));
+            let bbox = AABB::from_points(corner, corner + size);
+
+            let ray_t = Interval::new(0.001, rng.gen_range(1.0..20.));
+            if rays.iter().any(|ray| bbox.hit(ray, &ray_t)) {
+                assert!(packet.may_hit(&bbox, &ray_t));
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::vector::{Point, Vec3};
+use crate::sdf::DistanceFunction;
+
+use std::rc::Rc;
+
+fn mix(a: f64, b: f64, t: f64) -> f64 {
+    a * (1. - t) + b * t
+}
+
+pub struct Translate {
+    inner: Rc<dyn DistanceFunction>,
+    offset: Vec3,
+}
+
+impl Translate {
+    pub fn new(inner: Rc<dyn DistanceFunction>, offset: Vec3) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { inner, offset })
+    }
+}
+
+impl DistanceFunction for Translate {
+    fn distance(&self, p: &Point) -> f64 {
+        self.inner.distance(&(*p - self.offset))
+    }
+}
+
+/// Union of a and b, blended over a distance of `smoothness` (plain union when it is 0)
+pub struct SmoothUnion {
+    a: Rc<dyn DistanceFunction>,
+    b: Rc<dyn DistanceFunction>,
+    smoothness: f64,
+}
+
+impl SmoothUnion {
+    pub fn new(a: Rc<dyn DistanceFunction>, b: Rc<dyn DistanceFunction>, smoothness: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { a, b, smoothness })
+    }
+}
+
+impl DistanceFunction for SmoothUnion {
+    fn distance(&self, p: &Point) -> f64 {
+        let d1 = self.a.distance(p);
+        let d2 = self.b.distance(p);
+        if self.smoothness <= 0. {
+            return d1.min(d2);
+        }
+
+        let h = (0.5 + 0.5 * (d2 - d1) / self.smoothness).clamp(0., 1.);
+        mix(d2, d1, h) - self.smoothness * h * (1. - h)
+    }
+}
+
+/// a minus b, blended over a distance of `smoothness` (sharp when it is 0)
+pub struct Subtraction {
+    a: Rc<dyn DistanceFunction>,
+    b: Rc<dyn DistanceFunction>,
+    smoothness: f64,
+}
+
+impl Subtraction {
+    pub fn new(a: Rc<dyn DistanceFunction>, b: Rc<dyn DistanceFunction>, smoothness: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { a, b, smoothness })
+    }
+}
+
+impl DistanceFunction for Subtraction {
+    fn distance(&self, p: &Point) -> f64 {
+        let d1 = self.a.distance(p);
+        let d2 = self.b.distance(p);
+        if self.smoothness <= 0. {
+            return d1.max(-d2);
+        }
+
+        let h = (0.5 - 0.5 * (d1 + d2) / self.smoothness).clamp(0., 1.);
+        mix(d1, -d2, h) + self.smoothness * h * (1. - h)
+    }
+}
+
+/// Intersection of a and b, blended over a distance of `smoothness` (sharp when it is 0)
+pub struct Intersection {
+    a: Rc<dyn DistanceFunction>,
+    b: Rc<dyn DistanceFunction>,
+    smoothness: f64,
+}
+
+impl Intersection {
+    pub fn new(a: Rc<dyn DistanceFunction>, b: Rc<dyn DistanceFunction>, smoothness: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { a, b, smoothness })
+    }
+}
+
+impl DistanceFunction for Intersection {
+    fn distance(&self, p: &Point) -> f64 {
+        let d1 = self.a.distance(p);
+        let d2 = self.b.distance(p);
+        if self.smoothness <= 0. {
+            return d1.max(d2);
+        }
+
+        let h = (0.5 - 0.5 * (d2 - d1) / self.smoothness).clamp(0., 1.);
+        mix(d2, d1, h) + self.smoothness * h * (1. - h)
+    }
+}
+
+/// Infinite repetition of the inner shape, with one cell every `period` along each axis.
+/// A zero component disables the repetition along that axis.
+pub struct Repetition {
+    inner: Rc<dyn DistanceFunction>,
+    period: Vec3,
+}
+
+impl Repetition {
+    pub fn new(inner: Rc<dyn DistanceFunction>, period: Vec3) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { inner, period })
+    }
+
+    fn repeat(value: f64, period: f64) -> f64 {
+        if period <= 0. {
+            value
+        } else {
+            value - period * (value / period).round()
+        }
+    }
+}
+
+impl DistanceFunction for Repetition {
+    fn distance(&self, p: &Point) -> f64 {
+        let q = Vec3::new(
+            Self::repeat(p.x(), self.period.x()),
+            Self::repeat(p.y(), self.period.y()),
+            Self::repeat(p.z(), self.period.z()),
+        );
+        self.inner.distance(&q)
+    }
+}
+
+/// Twist around the y axis, of `rate` radians per unit of height.
+/// The result is not an exact distance anymore, so it needs a reduced step scale.
+pub struct Twist {
+    inner: Rc<dyn DistanceFunction>,
+    rate: f64,
+}
+
+impl Twist {
+    pub fn new(inner: Rc<dyn DistanceFunction>, rate: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { inner, rate })
+    }
+}
+
+impl DistanceFunction for Twist {
+    fn distance(&self, p: &Point) -> f64 {
+        let (s, c) = (self.rate * p.y()).sin_cos();
+        let q = Vec3::new(c * p.x() - s * p.z(), p.y(), s * p.x() + c * p.z());
+        self.inner.distance(&q)
+    }
+}
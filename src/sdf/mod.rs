@@ -0,0 +1,20 @@
+use crate::vector::Point;
+
+mod primitives;
+mod combinators;
+
+pub use primitives::{SdfSphere, SdfBox, RoundBox, Torus, Capsule, MengerSponge, Mandelbulb};
+pub use combinators::{Translate, SmoothUnion, Subtraction, Intersection, Repetition, Twist};
+
+/// Signed distance to a surface: negative inside, positive outside.
+/// The magnitude must never overestimate the distance to the surface, otherwise the
+/// sphere tracing can step through it (see `Sdf::with_step_scale` for functions that do).
+pub trait DistanceFunction {
+    fn distance(&self, p: &Point) -> f64;
+}
+
+impl<F: Fn(&Point) -> f64> DistanceFunction for F {
+    fn distance(&self, p: &Point) -> f64 {
+        self(p)
+    }
+}
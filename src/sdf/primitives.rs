@@ -0,0 +1,193 @@
+use crate::vector::{Point, Vec3};
+use crate::sdf::DistanceFunction;
+
+use std::rc::Rc;
+
+// All the primitives are centered on the origin, use `Translate` to move them around
+
+fn abs(p: &Vec3) -> Vec3 {
+    Vec3::new(p.x().abs(), p.y().abs(), p.z().abs())
+}
+
+fn positive_part(p: &Vec3) -> Vec3 {
+    Vec3::new(p.x().max(0.), p.y().max(0.), p.z().max(0.))
+}
+
+fn max_component(p: &Vec3) -> f64 {
+    p.x().max(p.y()).max(p.z())
+}
+
+fn box_distance(p: &Point, half_size: &Vec3) -> f64 {
+    let q = abs(p) - *half_size;
+    positive_part(&q).length() + max_component(&q).min(0.)
+}
+
+pub struct SdfSphere {
+    radius: f64,
+}
+
+impl SdfSphere {
+    pub fn new(radius: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { radius })
+    }
+}
+
+impl DistanceFunction for SdfSphere {
+    fn distance(&self, p: &Point) -> f64 {
+        p.length() - self.radius
+    }
+}
+
+pub struct SdfBox {
+    half_size: Vec3,
+}
+
+impl SdfBox {
+    pub fn new(size: Vec3) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { half_size: size / 2. })
+    }
+}
+
+impl DistanceFunction for SdfBox {
+    fn distance(&self, p: &Point) -> f64 {
+        box_distance(p, &self.half_size)
+    }
+}
+
+pub struct RoundBox {
+    half_size: Vec3,
+    radius: f64,
+}
+
+impl RoundBox {
+    pub fn new(size: Vec3, radius: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { half_size: size / 2., radius })
+    }
+}
+
+impl DistanceFunction for RoundBox {
+    fn distance(&self, p: &Point) -> f64 {
+        let r = Vec3::new(self.radius, self.radius, self.radius);
+        box_distance(p, &(self.half_size - r)) - self.radius
+    }
+}
+
+/// Torus lying in the xz plane
+pub struct Torus {
+    major_radius: f64,
+    minor_radius: f64,
+}
+
+impl Torus {
+    pub fn new(major_radius: f64, minor_radius: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { major_radius, minor_radius })
+    }
+}
+
+impl DistanceFunction for Torus {
+    fn distance(&self, p: &Point) -> f64 {
+        let radial = (p.x() * p.x() + p.z() * p.z()).sqrt() - self.major_radius;
+        (radial * radial + p.y() * p.y()).sqrt() - self.minor_radius
+    }
+}
+
+pub struct Capsule {
+    a: Point,
+    b: Point,
+    radius: f64,
+}
+
+impl Capsule {
+    pub fn new(a: Point, b: Point, radius: f64) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { a, b, radius })
+    }
+}
+
+impl DistanceFunction for Capsule {
+    fn distance(&self, p: &Point) -> f64 {
+        let pa = *p - self.a;
+        let ba = self.b - self.a;
+        let h = (pa.dot(&ba) / ba.dot(&ba)).clamp(0., 1.);
+        (pa - ba * h).length() - self.radius
+    }
+}
+
+/// Menger sponge fitting in a cube of side `size`
+pub struct MengerSponge {
+    half_size: f64,
+    iterations: usize,
+}
+
+impl MengerSponge {
+    pub fn new(size: f64, iterations: usize) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { half_size: size / 2., iterations })
+    }
+}
+
+impl DistanceFunction for MengerSponge {
+    fn distance(&self, p: &Point) -> f64 {
+        // Computed on the [-1, 1] cube, then scaled back
+        let p = *p / self.half_size;
+        let mut d = box_distance(&p, &Vec3::new(1., 1., 1.));
+
+        let mut scale = 1.;
+        for _ in 0..self.iterations {
+            let a = Vec3::new(
+                (p.x() * scale).rem_euclid(2.) - 1.,
+                (p.y() * scale).rem_euclid(2.) - 1.,
+                (p.z() * scale).rem_euclid(2.) - 1.,
+            );
+            scale *= 3.;
+
+            let r = abs(&(Vec3::new(1., 1., 1.) - 3. * abs(&a)));
+            let da = r.x().max(r.y());
+            let db = r.y().max(r.z());
+            let dc = r.z().max(r.x());
+            let c = (da.min(db).min(dc) - 1.) / scale;
+            d = d.max(c);
+        }
+
+        d * self.half_size
+    }
+}
+
+/// Mandelbulb fractal of unit radius (roughly), using the usual distance estimator
+pub struct Mandelbulb {
+    power: f64,
+    iterations: usize,
+}
+
+impl Mandelbulb {
+    pub fn new(power: f64, iterations: usize) -> Rc<dyn DistanceFunction> {
+        Rc::new(Self { power, iterations })
+    }
+}
+
+impl DistanceFunction for Mandelbulb {
+    fn distance(&self, p: &Point) -> f64 {
+        const BAILOUT: f64 = 2.;
+
+        let mut z = *p;
+        let mut dr = 1.;
+        let mut r = z.length();
+
+        for _ in 0..self.iterations {
+            r = z.length();
+            if r > BAILOUT || r == 0. {
+                break;
+            }
+
+            let theta = (z.z() / r).acos() * self.power;
+            let phi = z.y().atan2(z.x()) * self.power;
+            dr = r.powf(self.power - 1.) * self.power * dr + 1.;
+
+            let zr = r.powf(self.power);
+            z = zr * Vec3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos()) + *p;
+        }
+
+        if r == 0. {
+            return 0.;
+        }
+        0.5 * r.ln() * r / dr
+    }
+}
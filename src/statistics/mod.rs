@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+#[cfg(feature = "statistics")]
+use std::cell::{Cell, RefCell};
+#[cfg(feature = "statistics")]
+use std::time::Instant;
+
+mod report;
+
+pub use report::RenderStatistics;
+
+/// Whether the counters are collected: they only are with the `statistics` feature.
+/// Without it, every function of this module compiles to nothing (or just runs its closure).
+pub const ENABLED: bool = cfg!(feature = "statistics");
+
+/// Paths longer than the histogram share its last bucket
+pub const PATH_LENGTH_BUCKETS: usize = 64;
+
+/// Kinds of rays the counters are split by
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RayKind {
+    Primary,
+    Secondary,
+    /// Only traced by `bvh_benchmark` for now, the camera having no shadow rays
+    Shadow,
+}
+
+/// Timed phases of a render
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Load,
+    BvhBuild,
+    Render,
+    Save,
+}
+
+impl RayKind {
+    pub const ALL: [RayKind; 3] = [RayKind::Primary, RayKind::Secondary, RayKind::Shadow];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RayKind::Primary => "primary",
+            RayKind::Secondary => "secondary",
+            RayKind::Shadow => "shadow",
+        }
+    }
+}
+
+impl Phase {
+    pub const ALL: [Phase; 4] = [Phase::Load, Phase::BvhBuild, Phase::Render, Phase::Save];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Load => "load",
+            Phase::BvhBuild => "bvh_build",
+            Phase::Render => "render",
+            Phase::Save => "save",
+        }
+    }
+}
+
+// The renderer runs on a single thread: the counters are that thread's
+#[cfg(feature = "statistics")]
+thread_local! {
+    static STATISTICS: RefCell<RenderStatistics> = RefCell::new(RenderStatistics::default());
+    static CURRENT_KIND: Cell<RayKind> = const { Cell::new(RayKind::Primary) };
+}
+
+/// Counts rays about to be traced, the nodes and primitives tested next being counted for their kind
+#[inline]
+pub fn trace_rays(kind: RayKind, count: usize) {
+    #[cfg(feature = "statistics")]
+    {
+        CURRENT_KIND.with(|current| current.set(kind));
+        STATISTICS.with(|statistics| statistics.borrow_mut().rays[kind as usize] += count as u64);
+    }
+    #[cfg(not(feature = "statistics"))]
+    let _ = (kind, count);
+}
+
+#[inline]
+pub fn count_node_visits(count: usize) {
+    #[cfg(feature = "statistics")]
+    {
+        let kind = CURRENT_KIND.with(|current| current.get());
+        STATISTICS.with(|statistics| statistics.borrow_mut().node_visits[kind as usize] += count as u64);
+    }
+    #[cfg(not(feature = "statistics"))]
+    let _ = count;
+}
+
+#[inline]
+pub fn count_primitive_tests(count: usize) {
+    #[cfg(feature = "statistics")]
+    {
+        let kind = CURRENT_KIND.with(|current| current.get());
+        STATISTICS.with(|statistics| statistics.borrow_mut().primitive_tests[kind as usize] += count as u64);
+    }
+    #[cfg(not(feature = "statistics"))]
+    let _ = count;
+}
+
+/// Records a path that ended after `length` rays
+#[inline]
+pub fn end_path(length: usize) {
+    #[cfg(feature = "statistics")]
+    STATISTICS.with(|statistics| statistics.borrow_mut().path_lengths[length.min(PATH_LENGTH_BUCKETS - 1)] += 1);
+    #[cfg(not(feature = "statistics"))]
+    let _ = length;
+}
+
+/// Runs the closure, adding its duration to the phase
+#[inline]
+pub fn time<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "statistics")]
+    {
+        let start = Instant::now();
+        let result = f();
+        add_time(phase, start.elapsed());
+        result
+    }
+    #[cfg(not(feature = "statistics"))]
+    {
+        let _ = phase;
+        f()
+    }
+}
+
+#[inline]
+pub fn add_time(phase: Phase, duration: Duration) {
+    #[cfg(feature = "statistics")]
+    STATISTICS.with(|statistics| statistics.borrow_mut().phases[phase as usize] += duration);
+    #[cfg(not(feature = "statistics"))]
+    let _ = (phase, duration);
+}
+
+/// Everything counted since the start, or the last reset (all zeros without the feature)
+pub fn report() -> RenderStatistics {
+    #[cfg(feature = "statistics")]
+    return STATISTICS.with(|statistics| statistics.borrow().clone());
+    #[cfg(not(feature = "statistics"))]
+    RenderStatistics::default()
+}
+
+pub fn reset() {
+    #[cfg(feature = "statistics")]
+    STATISTICS.with(|statistics| *statistics.borrow_mut() = RenderStatistics::default());
+}
+
+#[cfg(all(test, feature = "statistics"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        reset();
+        trace_rays(RayKind::Primary, 2);
+        count_node_visits(5);
+        trace_rays(RayKind::Secondary, 1);
+        count_node_visits(3);
+        count_primitive_tests(4);
+        end_path(2);
+        end_path(1000);
+
+        let statistics = report();
+        assert_eq!(statistics.rays, [2, 1, 0]);
+        assert_eq!(statistics.node_visits, [5, 3, 0]);
+        assert_eq!(statistics.primitive_tests, [0, 4, 0]);
+        assert_eq!(statistics.path_lengths[2], 1);
+        assert_eq!(statistics.path_lengths[PATH_LENGTH_BUCKETS - 1], 1);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::statistics::{RayKind, Phase, PATH_LENGTH_BUCKETS};
+
+use std::fmt;
+use std::time::Duration;
+
+/// Counters of a render, indexed by `RayKind` and `Phase`. Node visits of packets count once for all their rays.
+#[derive(Clone, Debug)]
+pub struct RenderStatistics {
+    pub rays: [u64; 3],
+    pub node_visits: [u64; 3],
+    pub primitive_tests: [u64; 3],
+    /// Number of paths of every length, in rays
+    pub path_lengths: [u64; PATH_LENGTH_BUCKETS],
+    pub phases: [Duration; 4],
+}
+
+impl Default for RenderStatistics {
+    fn default() -> Self {
+        Self {
+            rays: [0; 3],
+            node_visits: [0; 3],
+            primitive_tests: [0; 3],
+            path_lengths: [0; PATH_LENGTH_BUCKETS],
+            phases: [Duration::ZERO; 4],
+        }
+    }
+}
+
+impl RenderStatistics {
+    pub fn total_rays(&self) -> u64 {
+        self.rays.iter().sum()
+    }
+
+    /// Rays traced per second of the render phase
+    pub fn rays_per_second(&self) -> f64 {
+        let render = self.phases[Phase::Render as usize].as_secs_f64();
+        if render > 0. { self.total_rays() as f64 / render } else { 0. }
+    }
+
+    pub fn mean_path_length(&self) -> f64 {
+        let paths: u64 = self.path_lengths.iter().sum();
+        let rays: u64 = self.path_lengths.iter().enumerate().map(|(length, &count)| length as u64 * count).sum();
+        if paths > 0 { rays as f64 / paths as f64 } else { 0. }
+    }
+
+    fn per_ray(counter: u64, rays: u64) -> f64 {
+        if rays > 0 { counter as f64 / rays as f64 } else { 0. }
+    }
+
+    pub fn to_json(&self) -> String {
+        let by_kind = |counters: &[u64; 3]| {
+            let fields: Vec<String> = RayKind::ALL.iter().map(|kind| format!("\"{}\": {}", kind.name(), counters[*kind as usize])).collect();
+            format!("{{{}}}", fields.join(", "))
+        };
+        let phases: Vec<String> = Phase::ALL.iter().map(|phase| format!("\"{}\": {}", phase.name(), self.phases[*phase as usize].as_secs_f64())).collect();
+        let path_lengths: Vec<String> = self.path_lengths.iter().map(|count| count.to_string()).collect();
+
+        format!(
+            "{{\n  \"rays\": {},\n  \"node_visits\": {},\n  \"primitive_tests\": {},\n  \"path_lengths\": [{}],\n  \"phases_seconds\": {{{}}},\n  \"rays_per_second\": {}\n}}\n",
+            by_kind(&self.rays),
+            by_kind(&self.node_visits),
+            by_kind(&self.primitive_tests),
+            path_lengths.join(", "),
+            phases.join(", "),
+            self.rays_per_second(),
+        )
+    }
+}
+
+impl fmt::Display for RenderStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Render statistics")?;
+        for phase in Phase::ALL {
+            writeln!(f, "  {:<12} {:>12.2?}", phase.name(), self.phases[phase as usize])?;
+        }
+
+        writeln!(f, "\n  {:<12} {:>14} {:>12} {:>12}", "rays", "count", "nodes/ray", "tests/ray")?;
+        for kind in RayKind::ALL {
+            let (rays, i) = (self.rays[kind as usize], kind as usize);
+            writeln!(f, "  {:<12} {:>14} {:>12.2} {:>12.2}", kind.name(), rays, Self::per_ray(self.node_visits[i], rays), Self::per_ray(self.primitive_tests[i], rays))?;
+        }
+        writeln!(f, "  {:<12} {:>14} {:>10.2} Mrays/s", "total", self.total_rays(), self.rays_per_second() / 1e6)?;
+
+        // Histogram up to the longest path, scaled to the most common length
+        writeln!(f, "\n  path lengths (mean {:.2})", self.mean_path_length())?;
+        let longest = self.path_lengths.iter().rposition(|&count| count > 0).unwrap_or(0);
+        let most = self.path_lengths.iter().max().copied().unwrap_or(0).max(1);
+        for (length, &count) in self.path_lengths.iter().enumerate().take(longest + 1).skip(1) {
+            let bar = "#".repeat((40 * count).div_ceil(most) as usize);
+            let label = if length == PATH_LENGTH_BUCKETS - 1 { format!("{}+", length) } else { length.to_string() };
+            writeln!(f, "  {:>4} {:>12} {}", label, count, bar)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let mut statistics = RenderStatistics {
+            rays: [100, 50, 0],
+            node_visits: [1000, 800, 0],
+            ..Default::default()
+        };
+        statistics.path_lengths[1] = 60;
+        statistics.path_lengths[2] = 40;
+        statistics.phases[Phase::Render as usize] = Duration::from_millis(500);
+
+        assert_eq!(statistics.rays_per_second(), 300.);
+        assert_eq!(statistics.mean_path_length(), 1.4);
+
+        let json = statistics.to_json();
+        assert!(json.contains("\"rays\": {\"primary\": 100, \"secondary\": 50, \"shadow\": 0}"));
+        assert!(json.contains("\"render\": 0.5"));
+        assert!(statistics.to_string().contains("primary"));
+    }
+}
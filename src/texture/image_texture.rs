@@ -30,8 +30,9 @@ impl Texture for ImageTexture {
         let u = uv.u.clamp(0., 1.);
         let v = 1.0 - uv.v.clamp(0., 1.); // Flip V (//! TODO: Why?)
 
-        let i: u32 = (u * self.width_f).floor() as u32;
-        let j: u32 = (v * self.height_f).floor() as u32;
+        // u = 1 and v = 0 fall on the last pixel instead of past the edge
+        let i: u32 = ((u * self.width_f).floor() as u32).min(self.image.width() - 1);
+        let j: u32 = ((v * self.height_f).floor() as u32).min(self.image.height() - 1);
         let pixel = self.image.get_pixel(i, j);
 
         let scale = 1. / 255.;
@@ -19,4 +19,10 @@ pub struct Uv {
 
 pub trait Texture {
     fn value(&self, uv: &Uv, p: &Vec3) -> Color;
+
+    /// Grayscale value, the mean of the color channels (for height maps)
+    fn scalar(&self, uv: &Uv, p: &Vec3) -> f64 {
+        let value = self.value(uv, p);
+        (value.r + value.g + value.b) / 3.
+    }
 }
\ No newline at end of file
@@ -18,6 +18,11 @@ impl SolidColor {
     pub fn from_rgb(r: f64, g: f64, b: f64) -> Rc<dyn Texture> {
         Self::new(Color::new(r, g, b))
     }
+
+    /// Constant scalar, for the textures driving a material parameter
+    pub fn gray(value: f64) -> Rc<dyn Texture> {
+        Self::from_rgb(value, value, value)
+    }
 }
 
 impl Texture for SolidColor {
@@ -0,0 +1,141 @@
+use crate::vector::{Point, Vec3};
+use crate::hittable::AABB;
+
+/// Affine transform, stored with its inverse: a linear part (as rows) followed by a translation
+#[derive(Clone, Copy, Debug)]
+pub struct Transform {
+    rows: [Vec3; 3],
+    translation: Vec3,
+    inverse_rows: [Vec3; 3],
+    inverse_translation: Vec3,
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Self::from_linear([Vec3::new(1., 0., 0.), Vec3::new(0., 1., 0.), Vec3::new(0., 0., 1.)], Vec3::zero())
+    }
+
+    pub fn translation(offset: Vec3) -> Self {
+        Self::identity().then_translate(offset)
+    }
+
+    pub fn scaling(factors: Vec3) -> Self {
+        Self::from_linear([
+            Vec3::new(factors.x(), 0., 0.),
+            Vec3::new(0., factors.y(), 0.),
+            Vec3::new(0., 0., factors.z()),
+        ], Vec3::zero())
+    }
+
+    /// Rotation of `angle_deg` degrees around `axis`, counter-clockwise when the axis points towards the viewer
+    pub fn rotation(axis: Vec3, angle_deg: f64) -> Self {
+        let a = axis.normalized();
+        let (s, c) = angle_deg.to_radians().sin_cos();
+        let t = 1. - c;
+        Self::from_linear([
+            Vec3::new(t * a.x() * a.x() + c, t * a.x() * a.y() - s * a.z(), t * a.x() * a.z() + s * a.y()),
+            Vec3::new(t * a.x() * a.y() + s * a.z(), t * a.y() * a.y() + c, t * a.y() * a.z() - s * a.x()),
+            Vec3::new(t * a.x() * a.z() - s * a.y(), t * a.y() * a.z() + s * a.x(), t * a.z() * a.z() + c),
+        ], Vec3::zero())
+    }
+
+    fn from_linear(rows: [Vec3; 3], translation: Vec3) -> Self {
+        // The inverse columns are the cross products of the rows
+        let [a, b, c] = rows;
+        let determinant = a.dot(&b.cross(&c));
+        let columns = [b.cross(&c) / determinant, c.cross(&a) / determinant, a.cross(&b) / determinant];
+        let inverse_rows = [0, 1, 2].map(|i| Vec3::new(columns[0].axis(i), columns[1].axis(i), columns[2].axis(i)));
+        let inverse_translation = -Self::apply(&inverse_rows, &translation);
+
+        Self { rows, translation, inverse_rows, inverse_translation }
+    }
+
+    fn apply(rows: &[Vec3; 3], v: &Vec3) -> Vec3 {
+        Vec3::new(rows[0].dot(v), rows[1].dot(v), rows[2].dot(v))
+    }
+
+    /// This transform followed by `next`
+    pub fn then(&self, next: &Transform) -> Self {
+        let columns = [0, 1, 2].map(|i| Vec3::new(self.rows[0].axis(i), self.rows[1].axis(i), self.rows[2].axis(i)));
+        let product = [0, 1, 2].map(|i| Vec3::new(next.rows[i].dot(&columns[0]), next.rows[i].dot(&columns[1]), next.rows[i].dot(&columns[2])));
+        Self::from_linear(product, next.point(&self.translation))
+    }
+
+    pub fn then_translate(&self, offset: Vec3) -> Self {
+        Self::from_linear(self.rows, self.translation + offset)
+    }
+
+    pub fn then_scale(&self, factors: Vec3) -> Self {
+        self.then(&Self::scaling(factors))
+    }
+
+    pub fn then_rotate(&self, axis: Vec3, angle_deg: f64) -> Self {
+        self.then(&Self::rotation(axis, angle_deg))
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self {
+            rows: self.inverse_rows,
+            translation: self.inverse_translation,
+            inverse_rows: self.rows,
+            inverse_translation: self.translation,
+        }
+    }
+
+    pub fn point(&self, p: &Point) -> Point {
+        Self::apply(&self.rows, p) + self.translation
+    }
+
+    pub fn vector(&self, v: &Vec3) -> Vec3 {
+        Self::apply(&self.rows, v)
+    }
+
+    /// Normals go through the inverse transpose, to stay orthogonal to the transformed surface
+    pub fn normal(&self, n: &Vec3) -> Vec3 {
+        n.x() * self.inverse_rows[0] + n.y() * self.inverse_rows[1] + n.z() * self.inverse_rows[2]
+    }
+
+    /// Box around the transformed corners of `bbox`
+    pub fn bounding_box(&self, bbox: &AABB) -> AABB {
+        let mut result = AABB::empty();
+        for corner in 0..8 {
+            let p = Point::new(
+                if corner & 1 == 0 { bbox.x().min } else { bbox.x().max },
+                if corner & 2 == 0 { bbox.y().min } else { bbox.y().max },
+                if corner & 4 == 0 { bbox.z().min } else { bbox.z().max },
+            );
+            let p = self.point(&p);
+            result = result.surrounding_box(&AABB::from_points(p, p));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation() {
+        let rotation = Transform::rotation(Vec3::new(0., 1., 0.), 90.);
+        assert!((rotation.vector(&Vec3::new(1., 0., 0.)) - Vec3::new(0., 0., -1.)).length() < 1e-9);
+        assert!((rotation.vector(&Vec3::new(0., 0., 1.)) - Vec3::new(1., 0., 0.)).length() < 1e-9);
+    }
+
+    #[test]
+    fn test_composition_and_inverse() {
+        let transform = Transform::scaling(Vec3::new(2., 1., 1.))
+            .then_rotate(Vec3::new(0., 0., 1.), 90.)
+            .then_translate(Vec3::new(1., 2., 3.));
+
+        let p = Point::new(1., 1., 0.);
+        let q = transform.point(&p);
+        assert!((q - Point::new(0., 4., 3.)).length() < 1e-9);
+        assert!((transform.inverse().point(&q) - p).length() < 1e-9);
+
+        // The normal of the plane x + y = 0 stays orthogonal to the transformed plane
+        let n = transform.normal(&Vec3::new(1., 1., 0.));
+        let tangent = transform.vector(&Vec3::new(1., -1., 0.));
+        assert!(n.dot(&tangent).abs() < 1e-9);
+    }
+}
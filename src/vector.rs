@@ -138,6 +138,14 @@ impl Vec3 {
         r_out_perp + r_out_parallel
     }
 
+    /// Two unit vectors (u, v) such that (u, v, self) is a right-handed orthonormal basis, self being a unit vector
+    pub fn orthonormal_basis(&self) -> (Vec3, Vec3) {
+        let helper = if self.x.abs() > 0.9 { Vec3::new(0., 1., 0.) } else { Vec3::new(1., 0., 0.) };
+        let v = self.cross(&helper).normalized();
+        let u = v.cross(self);
+        (u, v)
+    }
+
     pub fn near_zero(&self) -> bool {
         let epsilon = 1e-8;
         self.x.abs() < epsilon && self.y.abs() < epsilon && self.z.abs() < epsilon